@@ -0,0 +1,154 @@
+use crate::backend::Backend;
+use crate::style::{Color, Style};
+use crossterm::cursor::{Hide, MoveTo, Show};
+use crossterm::event::{DisableMouseCapture, EnableMouseCapture};
+use crossterm::style::{
+    self as term_style, Attribute, Print, SetAttribute, SetBackgroundColor, SetForegroundColor,
+};
+use crossterm::terminal::{
+    self, disable_raw_mode, enable_raw_mode, Clear, ClearType, EnterAlternateScreen,
+    LeaveAlternateScreen,
+};
+use crossterm::{execute, queue};
+use std::io::{self, Stdout, Write};
+
+pub struct CrosstermBackend {
+    stdout: Stdout,
+    style: Style,
+    active: bool,
+}
+
+impl CrosstermBackend {
+    pub fn new() -> Self {
+        CrosstermBackend {
+            stdout: io::stdout(),
+            style: Style::default(),
+            active: false,
+        }
+    }
+
+    fn set_style(&mut self, style: Style) -> io::Result<()> {
+        if style == self.style {
+            return Ok(());
+        }
+
+        // Attributes can't be switched off one by one portably, so start over from a reset
+        queue!(self.stdout, SetAttribute(Attribute::Reset))?;
+        if let Some(fg) = style.fg {
+            queue!(self.stdout, SetForegroundColor(to_crossterm_color(fg)))?;
+        }
+        if let Some(bg) = style.bg {
+            queue!(self.stdout, SetBackgroundColor(to_crossterm_color(bg)))?;
+        }
+        let attributes = [
+            (style.bold, Attribute::Bold),
+            (style.italic, Attribute::Italic),
+            (style.underline, Attribute::Underlined),
+            (style.dim, Attribute::Dim),
+            (style.reverse, Attribute::Reverse),
+        ];
+        for (enabled, attribute) in attributes {
+            if enabled {
+                queue!(self.stdout, SetAttribute(attribute))?;
+            }
+        }
+
+        self.style = style;
+        Ok(())
+    }
+}
+
+fn to_crossterm_color(color: Color) -> term_style::Color {
+    match color {
+        Color::Reset => term_style::Color::Reset,
+        Color::Black => term_style::Color::Black,
+        Color::Red => term_style::Color::DarkRed,
+        Color::Green => term_style::Color::DarkGreen,
+        Color::Yellow => term_style::Color::DarkYellow,
+        Color::Blue => term_style::Color::DarkBlue,
+        Color::Magenta => term_style::Color::DarkMagenta,
+        Color::Cyan => term_style::Color::DarkCyan,
+        Color::White => term_style::Color::Grey,
+        Color::BrightBlack => term_style::Color::DarkGrey,
+        Color::BrightRed => term_style::Color::Red,
+        Color::BrightGreen => term_style::Color::Green,
+        Color::BrightYellow => term_style::Color::Yellow,
+        Color::BrightBlue => term_style::Color::Blue,
+        Color::BrightMagenta => term_style::Color::Magenta,
+        Color::BrightCyan => term_style::Color::Cyan,
+        Color::BrightWhite => term_style::Color::White,
+        Color::Indexed(index) => term_style::Color::AnsiValue(index),
+        Color::Rgb(r, g, b) => term_style::Color::Rgb { r, g, b },
+    }
+}
+
+/// Puts the terminal back the way `CrosstermBackend::enter` found it.
+pub fn restore_terminal() -> io::Result<()> {
+    disable_raw_mode()?;
+    execute!(
+        io::stdout(),
+        LeaveAlternateScreen,
+        Show,
+        DisableMouseCapture
+    )
+}
+
+impl Default for CrosstermBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Backend for CrosstermBackend {
+    fn move_to(&mut self, x: usize, y: usize) -> io::Result<()> {
+        queue!(self.stdout, MoveTo(x as u16, y as u16))
+    }
+
+    fn print(&mut self, text: &str, style: Style) -> io::Result<()> {
+        self.set_style(style)?;
+        queue!(self.stdout, Print(text))
+    }
+
+    fn clear(&mut self) -> io::Result<()> {
+        queue!(self.stdout, Clear(ClearType::All))
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        // Leave the terminal unstyled between frames so nothing bleeds into later output
+        self.set_style(Style::default())?;
+        self.stdout.flush()
+    }
+
+    fn size(&self) -> io::Result<(usize, usize)> {
+        let (width, height) = terminal::size()?;
+        Ok((width as usize, height as usize))
+    }
+
+    fn enter(&mut self) -> io::Result<()> {
+        self.active = true;
+        execute!(self.stdout, EnableMouseCapture, Hide, EnterAlternateScreen)?;
+        enable_raw_mode()
+    }
+
+    fn leave(&mut self) -> io::Result<()> {
+        if !self.active {
+            return Ok(());
+        }
+        self.active = false;
+        restore_terminal()
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+impl Drop for CrosstermBackend {
+    fn drop(&mut self) {
+        let _ = self.leave();
+    }
+}
@@ -0,0 +1,39 @@
+pub mod crossterm;
+pub mod test;
+
+use crate::style::Style;
+use std::io;
+
+/// Where frames end up. Objects draw through this, and the handler flushes its diffs into one.
+pub trait Backend {
+    fn move_to(&mut self, x: usize, y: usize) -> io::Result<()>;
+
+    fn print(&mut self, text: &str, style: Style) -> io::Result<()>;
+
+    /// Like `print`, but box drawing characters join up with lines already drawn
+    /// underneath, turning e.g. `┐` on `─` into `┬`. Backends that can't read back what
+    /// they drew just print.
+    fn print_joined(&mut self, text: &str, style: Style) -> io::Result<()> {
+        self.print(text, style)
+    }
+
+    fn clear(&mut self) -> io::Result<()>;
+
+    fn flush(&mut self) -> io::Result<()>;
+
+    fn size(&self) -> io::Result<(usize, usize)>;
+
+    /// Prepares the output for drawing, e.g. raw mode and the alternate screen.
+    fn enter(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+
+    /// Undoes `enter`. Must be safe to call more than once.
+    fn leave(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any;
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any;
+}
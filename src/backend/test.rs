@@ -0,0 +1,127 @@
+use crate::backend::Backend;
+use crate::buffer::Buffer;
+use crate::style::Style;
+use std::io;
+
+/// A headless backend that records everything written to it in a cell grid.
+pub struct TestBackend {
+    buffer: Buffer,
+}
+
+impl TestBackend {
+    pub fn new(width: usize, height: usize) -> Self {
+        TestBackend {
+            buffer: Buffer::new((width, height)),
+        }
+    }
+
+    pub fn buffer(&self) -> &Buffer {
+        &self.buffer
+    }
+
+    pub fn resize(&mut self, width: usize, height: usize) {
+        self.buffer = Buffer::new((width, height));
+    }
+
+    /// The symbols of row `y` joined into a string, handy for assertions.
+    pub fn line(&self, y: usize) -> String {
+        (0..self.buffer.size.0)
+            .filter_map(|x| self.buffer.get(x, y))
+            .map(|cell| cell.symbol.as_str())
+            .collect()
+    }
+
+    pub fn lines(&self) -> Vec<String> {
+        (0..self.buffer.size.1).map(|y| self.line(y)).collect()
+    }
+}
+
+impl Backend for TestBackend {
+    fn move_to(&mut self, x: usize, y: usize) -> io::Result<()> {
+        self.buffer.move_to(x, y)
+    }
+
+    fn print(&mut self, text: &str, style: Style) -> io::Result<()> {
+        self.buffer.print(text, style)
+    }
+
+    fn print_joined(&mut self, text: &str, style: Style) -> io::Result<()> {
+        self.buffer.print_joined(text, style)
+    }
+
+    fn clear(&mut self) -> io::Result<()> {
+        self.buffer.clear()
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn size(&self) -> io::Result<(usize, usize)> {
+        Ok(self.buffer.size)
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TestBackend;
+    use crate::objects::container::ContainerObject;
+    use crate::objects::text::TextObject;
+    use crate::objects::{Handle, ObjectCommand};
+    use crate::TuiHandler;
+
+    fn backend(tui: &TuiHandler) -> &TestBackend {
+        tui.backend()
+            .as_any()
+            .downcast_ref::<TestBackend>()
+            .unwrap()
+    }
+
+    #[test]
+    fn draws_bordered_text_and_flushes_changes() {
+        let mut tui = TuiHandler::with_backend(Box::new(TestBackend::new(12, 5)));
+        let root = Handle {
+            indexes: Vec::new(),
+        };
+        let frame = tui
+            .with(&root)
+            .handle(ObjectCommand::AddObject(ContainerObject::new()))
+            .unwrap()
+            .unwrap_handle();
+        let text = tui
+            .with(&frame)
+            .handle(ObjectCommand::AddObject(TextObject::new("hi 世界")))
+            .unwrap()
+            .unwrap_handle();
+
+        tui.draw().unwrap();
+        assert_eq!(
+            backend(&tui).lines(),
+            [
+                "            ",
+                " ╭────────╮ ",
+                " │hi 世界 │ ",
+                " ╰────────╯ ",
+                "            ",
+            ]
+        );
+        // Wide characters leave an empty continuation cell behind them
+        assert_eq!(backend(&tui).buffer().get(5, 2).unwrap().symbol, "世");
+        assert_eq!(backend(&tui).buffer().get(6, 2).unwrap().symbol, "");
+
+        tui.with(&text)
+            .handle(ObjectCommand::SetText("ok".to_string()))
+            .unwrap();
+        tui.draw().unwrap();
+        assert_eq!(backend(&tui).line(2), " │ok      │ ");
+        assert_eq!(backend(&tui).buffer().get(6, 2).unwrap().symbol, " ");
+    }
+}
@@ -0,0 +1,160 @@
+use crate::layout::Sides;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BorderStyle {
+    Plain,
+    #[default]
+    Rounded,
+    Double,
+    Thick,
+    /// `+`, `-` and `|` only, for terminals without box drawing characters.
+    Ascii,
+    None,
+}
+
+/// The characters a border is drawn with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BorderGlyphs {
+    pub top_left: &'static str,
+    pub top_right: &'static str,
+    pub bottom_left: &'static str,
+    pub bottom_right: &'static str,
+    pub horizontal: &'static str,
+    pub vertical: &'static str,
+}
+
+impl BorderStyle {
+    pub fn glyphs(self) -> Option<BorderGlyphs> {
+        let (top_left, top_right, bottom_left, bottom_right, horizontal, vertical) = match self {
+            BorderStyle::Plain => ("┌", "┐", "└", "┘", "─", "│"),
+            BorderStyle::Rounded => ("╭", "╮", "╰", "╯", "─", "│"),
+            BorderStyle::Double => ("╔", "╗", "╚", "╝", "═", "║"),
+            BorderStyle::Thick => ("┏", "┓", "┗", "┛", "━", "┃"),
+            BorderStyle::Ascii => ("+", "+", "+", "+", "-", "|"),
+            BorderStyle::None => return None,
+        };
+        Some(BorderGlyphs {
+            top_left,
+            top_right,
+            bottom_left,
+            bottom_right,
+            horizontal,
+            vertical,
+        })
+    }
+}
+
+/// Which sides of a container get a border.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Borders {
+    pub top: bool,
+    pub right: bool,
+    pub bottom: bool,
+    pub left: bool,
+}
+
+impl Borders {
+    pub const ALL: Borders = Borders {
+        top: true,
+        right: true,
+        bottom: true,
+        left: true,
+    };
+
+    pub const NONE: Borders = Borders {
+        top: false,
+        right: false,
+        bottom: false,
+        left: false,
+    };
+
+    /// One cell of inset for every side that is on.
+    pub fn insets(self) -> Sides {
+        Sides::new(
+            self.top as usize,
+            self.right as usize,
+            self.bottom as usize,
+            self.left as usize,
+        )
+    }
+}
+
+impl Default for Borders {
+    fn default() -> Self {
+        Borders::ALL
+    }
+}
+
+// Each line glyph as the arms it has, as (up, down, left, right) bits, per family. Rounded
+// corners join like light ones.
+const LIGHT: [(&str, u8); 15] = [
+    ("─", 0b0011),
+    ("│", 0b1100),
+    ("┌", 0b0101),
+    ("┐", 0b0110),
+    ("└", 0b1001),
+    ("┘", 0b1010),
+    ("├", 0b1101),
+    ("┤", 0b1110),
+    ("┬", 0b0111),
+    ("┴", 0b1011),
+    ("┼", 0b1111),
+    ("╭", 0b0101),
+    ("╮", 0b0110),
+    ("╰", 0b1001),
+    ("╯", 0b1010),
+];
+const HEAVY: [(&str, u8); 11] = [
+    ("━", 0b0011),
+    ("┃", 0b1100),
+    ("┏", 0b0101),
+    ("┓", 0b0110),
+    ("┗", 0b1001),
+    ("┛", 0b1010),
+    ("┣", 0b1101),
+    ("┫", 0b1110),
+    ("┳", 0b0111),
+    ("┻", 0b1011),
+    ("╋", 0b1111),
+];
+const DOUBLE: [(&str, u8); 11] = [
+    ("═", 0b0011),
+    ("║", 0b1100),
+    ("╔", 0b0101),
+    ("╗", 0b0110),
+    ("╚", 0b1001),
+    ("╝", 0b1010),
+    ("╠", 0b1101),
+    ("╣", 0b1110),
+    ("╦", 0b0111),
+    ("╩", 0b1011),
+    ("╬", 0b1111),
+];
+const ASCII: [(&str, u8); 3] = [("-", 0b0011), ("|", 0b1100), ("+", 0b1111)];
+
+fn arms(table: &[(&'static str, u8)], glyph: &str) -> Option<u8> {
+    table
+        .iter()
+        .find(|(g, _)| *g == glyph)
+        .map(|(_, arms)| *arms)
+}
+
+/// The glyph to draw when line glyph `new` lands on line glyph `existing`, e.g. `┐` on `─`
+/// gives `┬`. Returns `None` when the two can't be joined (different line families or not
+/// line glyphs at all), in which case `new` should simply replace `existing`.
+pub fn join(existing: &str, new: &str) -> Option<&'static str> {
+    for table in [&LIGHT[..], &HEAVY[..], &DOUBLE[..], &ASCII[..]] {
+        if let (Some(a), Some(b)) = (arms(table, existing), arms(table, new)) {
+            let merged = a | b;
+            if merged == b {
+                // Nothing new was added, so keep `new` as is (this keeps rounded corners)
+                return table.iter().find(|(g, _)| *g == new).map(|(g, _)| *g);
+            }
+            return table
+                .iter()
+                .find(|(_, arms)| *arms == merged)
+                .map(|(g, _)| *g);
+        }
+    }
+    None
+}
@@ -0,0 +1,163 @@
+use crate::backend::Backend;
+use crate::border::join;
+use crate::style::Style;
+use crate::wrap::grapheme_width;
+use std::io;
+use unicode_segmentation::UnicodeSegmentation;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cell {
+    pub symbol: String,
+    pub style: Style,
+}
+
+impl Default for Cell {
+    fn default() -> Self {
+        Cell {
+            symbol: " ".to_string(),
+            style: Style::default(),
+        }
+    }
+}
+
+/// A grid of cells that objects draw into before anything reaches the terminal.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Buffer {
+    pub size: (usize, usize),
+    pub cells: Vec<Cell>,
+    cursor: (usize, usize),
+}
+
+impl Buffer {
+    pub fn new(size: (usize, usize)) -> Self {
+        Buffer {
+            size,
+            cells: vec![Cell::default(); size.0 * size.1],
+            cursor: (0, 0),
+        }
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&Cell> {
+        if x < self.size.0 && y < self.size.1 {
+            Some(&self.cells[y * self.size.0 + x])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut Cell> {
+        if x < self.size.0 && y < self.size.1 {
+            Some(&mut self.cells[y * self.size.0 + x])
+        } else {
+            None
+        }
+    }
+
+    fn set_symbol(&mut self, x: usize, y: usize, symbol: &str, style: Style) {
+        if x >= self.size.0 || y >= self.size.1 {
+            return;
+        }
+        let row = y * self.size.0;
+
+        // Overwriting part of a wide character blanks the rest of it
+        if !symbol.is_empty() {
+            let mut lead = x;
+            while lead > 0 && self.cells[row + lead].symbol.is_empty() {
+                lead -= 1;
+            }
+            for cell in &mut self.cells[row + lead..row + x] {
+                cell.symbol = " ".to_string();
+            }
+        }
+        let mut next = x + 1;
+        while next < self.size.0 && self.cells[row + next].symbol.is_empty() {
+            self.cells[row + next].symbol = " ".to_string();
+            next += 1;
+        }
+
+        let cell = &mut self.cells[row + x];
+        cell.symbol = symbol.to_string();
+        cell.style = style;
+    }
+
+    /// Returns every cell of `next` that differs from `self`, as `(x, y, cell)`.
+    pub fn diff<'a>(&self, next: &'a Buffer) -> Vec<(usize, usize, &'a Cell)> {
+        let mut changes = Vec::new();
+        for (i, cell) in next.cells.iter().enumerate() {
+            if self.size != next.size || self.cells[i] != *cell {
+                changes.push((i % next.size.0, i / next.size.0, cell));
+            }
+        }
+        changes
+    }
+}
+
+impl Backend for Buffer {
+    fn move_to(&mut self, x: usize, y: usize) -> io::Result<()> {
+        self.cursor = (x, y);
+        Ok(())
+    }
+
+    /// Writes `text` at the cursor and advances it. Anything past the right edge is dropped.
+    /// Wide graphemes take two cells; the second one is left with an empty symbol.
+    fn print(&mut self, text: &str, style: Style) -> io::Result<()> {
+        let (mut x, y) = self.cursor;
+        for grapheme in text.graphemes(true) {
+            if grapheme == "\n" || grapheme == "\r\n" {
+                break;
+            }
+            let width = grapheme_width(grapheme);
+            if width == 0 {
+                continue;
+            }
+
+            if width > 1 && x + width > self.size.0 {
+                // Half a wide character can't be drawn, so pad the edge instead
+                self.set_symbol(x, y, " ", style);
+            } else {
+                self.set_symbol(x, y, grapheme, style);
+                for i in 1..width {
+                    self.set_symbol(x + i, y, "", style);
+                }
+            }
+            x += width;
+        }
+        self.cursor = (x, y);
+        Ok(())
+    }
+
+    fn print_joined(&mut self, text: &str, style: Style) -> io::Result<()> {
+        let (mut x, y) = self.cursor;
+        for grapheme in text.graphemes(true) {
+            let joined = self
+                .get(x, y)
+                .and_then(|cell| join(&cell.symbol, grapheme))
+                .unwrap_or(grapheme);
+            self.move_to(x, y)?;
+            self.print(joined, style)?;
+            x = self.cursor.0;
+        }
+        Ok(())
+    }
+
+    fn clear(&mut self) -> io::Result<()> {
+        self.cells.fill(Cell::default());
+        Ok(())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn size(&self) -> io::Result<(usize, usize)> {
+        Ok(self.size)
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
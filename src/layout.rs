@@ -0,0 +1,263 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HorizontalAlignment {
+    #[default]
+    Left,
+    Center,
+    Right,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum VerticalAlignment {
+    #[default]
+    Top,
+    Middle,
+    Bottom,
+}
+
+impl HorizontalAlignment {
+    /// Offset that places something `used` wide inside `available`.
+    pub fn offset(self, available: usize, used: usize) -> usize {
+        let free = available.saturating_sub(used);
+        match self {
+            HorizontalAlignment::Left => 0,
+            HorizontalAlignment::Center => free / 2,
+            HorizontalAlignment::Right => free,
+        }
+    }
+}
+
+impl VerticalAlignment {
+    /// Offset that places something `used` tall inside `available`.
+    pub fn offset(self, available: usize, used: usize) -> usize {
+        let free = available.saturating_sub(used);
+        match self {
+            VerticalAlignment::Top => 0,
+            VerticalAlignment::Middle => free / 2,
+            VerticalAlignment::Bottom => free,
+        }
+    }
+}
+
+/// How much room a child gets along its container's flow direction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Constraint {
+    /// Exactly this many cells.
+    Length(usize),
+    /// A percentage of the container.
+    Percentage(usize),
+    /// `a / b` of the container.
+    Ratio(usize, usize),
+    /// At least this many cells, and a weight-1 share of whatever is left.
+    Min(usize),
+    /// A weight-1 share of whatever is left, but never more than this many cells.
+    Max(usize),
+    /// A share of whatever is left, proportional to the weight.
+    Fill(usize),
+}
+
+/// Splits `total` cells between `constraints`, returning one extent per constraint.
+///
+/// Fixed sizes are handed out first, in order, and cut short once `total` runs out. The
+/// rest goes to `Fill`, `Min` and `Max` by weight. If nothing can grow, the space after the
+/// last child stays empty.
+pub fn solve(total: usize, constraints: &[Constraint]) -> Vec<usize> {
+    let mut sizes: Vec<usize> = constraints
+        .iter()
+        .map(|constraint| match *constraint {
+            Constraint::Length(n) | Constraint::Min(n) => n,
+            Constraint::Percentage(p) => total * p.min(100) / 100,
+            Constraint::Ratio(_, 0) => 0,
+            Constraint::Ratio(a, b) => total * a.min(b) / b,
+            Constraint::Max(_) | Constraint::Fill(_) => 0,
+        })
+        .collect();
+
+    let mut remaining = total;
+    for size in &mut sizes {
+        *size = (*size).min(remaining);
+        remaining -= *size;
+    }
+
+    // (index, weight, cap)
+    let mut flexible: Vec<(usize, usize, Option<usize>)> = constraints
+        .iter()
+        .enumerate()
+        .filter_map(|(i, constraint)| match *constraint {
+            Constraint::Fill(weight) => Some((i, weight, None)),
+            Constraint::Min(_) => Some((i, 1, None)),
+            Constraint::Max(cap) => Some((i, 1, Some(cap))),
+            _ => None,
+        })
+        .collect();
+
+    // A `Max` whose share would go past its cap takes the cap and drops out, which frees
+    // more room for the others, so keep going until every share fits
+    loop {
+        let weight: usize = flexible.iter().map(|&(_, w, _)| w).sum();
+        if weight == 0 || remaining == 0 {
+            break;
+        }
+        let capped = flexible
+            .iter()
+            .position(|&(_, w, cap)| cap.is_some_and(|cap| cap * weight < remaining * w));
+        match capped {
+            Some(k) => {
+                let (i, _, cap) = flexible.remove(k);
+                sizes[i] = cap.unwrap();
+                remaining -= sizes[i];
+            }
+            None => break,
+        }
+    }
+
+    distribute(remaining, &flexible, &mut sizes);
+    sizes
+}
+
+fn distribute(remaining: usize, flexible: &[(usize, usize, Option<usize>)], sizes: &mut [usize]) {
+    let weight: usize = flexible.iter().map(|&(_, w, _)| w).sum();
+    if weight == 0 {
+        return;
+    }
+
+    let mut handed_out = 0;
+    for &(i, w, _) in flexible {
+        let share = remaining * w / weight;
+        sizes[i] += share;
+        handed_out += share;
+    }
+
+    // Rounding down leaves fewer cells over than there are children. They go one each to
+    // the children that lost the most to rounding, earlier children first on ties, so equal
+    // weights never differ by more than a cell and the result doesn't depend on anything
+    // but the inputs
+    let mut order: Vec<&(usize, usize, Option<usize>)> = flexible.iter().collect();
+    order.sort_by_key(|&&(i, w, _)| (std::cmp::Reverse(remaining * w % weight), i));
+    for &&(i, _, _) in order.iter().take(remaining - handed_out) {
+        sizes[i] += 1;
+    }
+}
+
+/// Per-side amounts of space, in cells.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Sides {
+    pub top: usize,
+    pub right: usize,
+    pub bottom: usize,
+    pub left: usize,
+}
+
+impl Sides {
+    pub fn new(top: usize, right: usize, bottom: usize, left: usize) -> Self {
+        Sides {
+            top,
+            right,
+            bottom,
+            left,
+        }
+    }
+
+    pub fn all(n: usize) -> Self {
+        Sides::new(n, n, n, n)
+    }
+
+    pub fn symmetric(vertical: usize, horizontal: usize) -> Self {
+        Sides::new(vertical, horizontal, vertical, horizontal)
+    }
+
+    /// Insets the rectangle at `position` with `size` by these amounts.
+    pub fn shrink(
+        &self,
+        position: (usize, usize),
+        size: (usize, usize),
+    ) -> ((usize, usize), (usize, usize)) {
+        let left = self.left.min(size.0);
+        let top = self.top.min(size.1);
+        (
+            (position.0 + left, position.1 + top),
+            (
+                size.0.saturating_sub(self.left + self.right),
+                size.1.saturating_sub(self.top + self.bottom),
+            ),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{solve, Constraint};
+
+    /// Every weight vector of up to four children with weights 1 to 4.
+    fn weight_vectors() -> Vec<Vec<usize>> {
+        let mut vectors = vec![Vec::new()];
+        let mut all = Vec::new();
+        for _ in 0..4 {
+            vectors = vectors
+                .iter()
+                .flat_map(|v| {
+                    (1..=4).map(move |w| {
+                        let mut v = v.clone();
+                        v.push(w);
+                        v
+                    })
+                })
+                .collect();
+            all.extend(vectors.clone());
+        }
+        all
+    }
+
+    #[test]
+    fn fill_extents_tile_the_total() {
+        for total in 0..=50 {
+            for weights in weight_vectors() {
+                let constraints: Vec<_> = weights.iter().map(|&w| Constraint::Fill(w)).collect();
+                let extents = solve(total, &constraints);
+                assert_eq!(extents.len(), constraints.len());
+                assert_eq!(extents.iter().sum::<usize>(), total, "{total} {weights:?}");
+                assert_eq!(extents, solve(total, &constraints));
+            }
+        }
+    }
+
+    #[test]
+    fn equal_weights_differ_by_at_most_one() {
+        for total in 0..=50 {
+            for count in 1..=7 {
+                for weight in 1..=3 {
+                    let extents = solve(total, &vec![Constraint::Fill(weight); count]);
+                    let min = extents.iter().min().unwrap();
+                    let max = extents.iter().max().unwrap();
+                    assert!(max - min <= 1, "{total} {count} {extents:?}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn mixed_constraints_tile_the_total_when_something_fills() {
+        let fixed = [
+            Constraint::Length(3),
+            Constraint::Percentage(30),
+            Constraint::Ratio(1, 3),
+            Constraint::Min(4),
+            Constraint::Max(2),
+        ];
+        for total in 0..=50 {
+            for weights in weight_vectors() {
+                let mut constraints: Vec<_> =
+                    weights.iter().map(|&w| Constraint::Fill(w)).collect();
+                for (i, constraint) in fixed.iter().enumerate().take(weights.len()) {
+                    constraints.insert(i * 2, *constraint);
+                }
+                let extents = solve(total, &constraints);
+                assert_eq!(
+                    extents.iter().sum::<usize>(),
+                    total,
+                    "{total} {constraints:?}"
+                );
+                assert_eq!(extents, solve(total, &constraints));
+            }
+        }
+    }
+}
@@ -1,41 +1,85 @@
+pub mod backend;
+pub mod border;
+pub mod buffer;
+pub mod layout;
 pub mod objects;
+pub mod style;
+pub mod theme;
+use crate::backend::crossterm::CrosstermBackend;
+use crate::backend::Backend;
+use crate::buffer::Buffer;
 use crate::objects::button::ButtonObject;
 use crate::objects::container::*;
+use crate::objects::dialog::DialogObject;
+use crate::objects::scroll_view::ScrollViewObject;
+use crate::objects::stack::StackObject;
 use crate::objects::*;
-
-use term_size::dimensions;
+use crate::theme::Theme;
 
 pub mod terminal;
 
-use crossterm::cursor::{Hide, Show};
-use crossterm::execute;
-use crossterm::terminal::{
-    disable_raw_mode, enable_raw_mode, Clear, EnterAlternateScreen, LeaveAlternateScreen,
-};
+use std::collections::HashSet;
 use std::io;
+use std::time::{Duration, Instant};
 
 pub mod triggers;
+pub mod wrap;
 use crate::triggers::Trigger;
 
+/// How often `run` sends `Tick` to the objects.
+pub const TICK_RATE: Duration = Duration::from_millis(100);
+
+/// Ticks `obj` and everything shown inside it. Returns whether any of them took it.
+fn tick_object(obj: &mut dyn Object) -> bool {
+    let mut ticked = obj.handle(ObjectCommand::Tick()).is_ok();
+    let mut index = 0;
+    while obj.child(index).is_some() {
+        if obj.child_visible(index) {
+            ticked |= tick_object(obj.child_mut(index).unwrap());
+        }
+        index += 1;
+    }
+    ticked
+}
+
 pub struct TuiHandler {
     pub objects: Box<dyn Object>,
     triggers: Vec<(Trigger, Box<dyn FnMut(&mut TuiHandler)>)>,
     pub selected: Handle,
     terminal_size: Option<(usize, usize)>,
     pub changed: bool,
+    buffer: Buffer,
+    backend: Box<dyn Backend>,
+    running: bool,
+    pub theme: Theme,
+    /// The open dialog and the selection it took the focus from.
+    dialog: Option<(Box<DialogObject>, Handle)>,
+    /// Keys whose press an object took, so their releases don't fire triggers. Several
+    /// can be down at once while typing.
+    consumed_keys: HashSet<crossterm::event::KeyCode>,
 }
 
 impl TuiHandler {
     pub fn new() -> Self {
-        let size = dimensions();
+        Self::with_backend(Box::new(CrosstermBackend::new()))
+    }
+
+    pub fn with_backend(backend: Box<dyn Backend>) -> Self {
+        let size = backend.size().ok();
         let mut tui = TuiHandler {
             objects: ContainerObject::new(),
             triggers: Vec::new(),
             selected: Handle {
                 indexes: Vec::new(),
             },
-            terminal_size: dimensions(),
+            terminal_size: size,
             changed: true,
+            buffer: Buffer::new((0, 0)),
+            backend,
+            running: false,
+            theme: Theme::default(),
+            dialog: None,
+            consumed_keys: HashSet::new(),
         };
         tui.objects
             .as_any_mut()
@@ -48,42 +92,366 @@ impl TuiHandler {
         tui
     }
 
-    pub fn run(&mut self) {
-        execute!(std::io::stdout(), crossterm::event::EnableMouseCapture).unwrap();
-        execute!(io::stdout(), Hide).unwrap();
-        execute!(io::stdout(), EnterAlternateScreen).unwrap();
-        enable_raw_mode().unwrap();
+    /// Takes over the terminal and processes events until a callback calls `quit`.
+    /// The terminal is restored before returning, whether or not the loop failed.
+    pub fn run(&mut self) -> io::Result<()> {
+        terminal::install_panic_hook();
+        self.backend.enter()?;
+        self.running = true;
 
-        loop {
-            self.handle_term_events();
+        let result = self.event_loop();
+        self.running = false;
+
+        let restored = self.backend.leave();
+        result.and(restored)
+    }
+
+    fn event_loop(&mut self) -> io::Result<()> {
+        let mut last_tick = Instant::now();
+        while self.running {
             if self.changed {
-                execute!(io::stdout(), Clear(crossterm::terminal::ClearType::All)).unwrap();
-                let container = self
-                    .objects
-                    .as_any_mut()
-                    .downcast_mut::<ContainerObject>()
-                    .unwrap();
-                container.update_sizes();
-                for obj in &container.content {
-                    obj.display();
+                self.draw()?;
+            }
+            self.handle_term_events()?;
+            if last_tick.elapsed() >= TICK_RATE {
+                self.tick();
+                last_tick = Instant::now();
+            }
+        }
+        Ok(())
+    }
+
+    /// Sends `Tick` to every visible object so animations, like an indeterminate progress
+    /// bar, move on a step. `run` does this every `TICK_RATE`; an app drawing from its own
+    /// loop calls it at whatever pace it likes.
+    pub fn tick(&mut self) {
+        if tick_object(&mut *self.objects) {
+            self.changed = true;
+        }
+    }
+
+    /// Asks `run` to return once the current event has been handled.
+    pub fn quit(&mut self) {
+        self.running = false;
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.running
+    }
+
+    pub fn draw(&mut self) -> io::Result<()> {
+        self.changed = false;
+        let container = self
+            .objects
+            .as_any_mut()
+            .downcast_mut::<ContainerObject>()
+            .unwrap();
+        container.update_sizes();
+
+        let mut buffer = Buffer::new(container.size);
+        for obj in &container.content {
+            obj.display(&mut buffer, &self.theme)?;
+        }
+
+        if let Some((dialog, _)) = &mut self.dialog {
+            dialog
+                .handle(ObjectCommand::SetSize(container.size))
+                .unwrap();
+            dialog
+                .handle(ObjectCommand::SetPosition(container.position))
+                .unwrap();
+            dialog.update_sizes();
+            dialog.display(&mut buffer, &self.theme)?;
+        }
+
+        if self.buffer.size != buffer.size {
+            // Nothing on screen can be trusted after a resize, so start from a blank frame
+            self.backend.clear()?;
+            self.buffer = Buffer::new(buffer.size);
+        }
+
+        self.flush_buffer(buffer)
+    }
+
+    /// Writes the cells that differ between `buffer` and what's on screen, then keeps
+    /// `buffer` as the new screen.
+    fn flush_buffer(&mut self, buffer: Buffer) -> io::Result<()> {
+        for (x, y, cell) in self.buffer.diff(&buffer) {
+            if cell.symbol.is_empty() {
+                // Covered by the wide character to its left
+                continue;
+            }
+            self.backend.move_to(x, y)?;
+            self.backend.print(&cell.symbol, cell.style)?;
+        }
+        self.backend.flush()?;
+
+        self.buffer = buffer;
+        Ok(())
+    }
+
+    /// Redraws just the object at `handle` on top of the last frame. Falls back to a
+    /// full `draw` when the layout is stale, a dialog is open, nothing was drawn yet or
+    /// something may be drawn over the object.
+    pub fn redraw_object(&mut self, handle: &Handle) -> io::Result<()> {
+        let root_size = self
+            .objects
+            .handle(ObjectCommand::GetSize())
+            .map(HandleReturn::unwrap_size)
+            .unwrap_or_default();
+        // Inside a scroll view the object has to be drawn through it to be clipped
+        if self.changed
+            || self.dialog.is_some()
+            || self.buffer.size != root_size
+            || self.scroll_offset_at(handle).is_some()
+            || self.is_covered(handle)
+        {
+            return self.draw();
+        }
+        if !self.is_visible(handle) {
+            return Ok(());
+        }
+
+        let mut buffer = self.buffer.clone();
+        let theme = self.theme.clone();
+        let obj = self.with(handle);
+        let (x, y) = obj
+            .handle(ObjectCommand::GetPosition())
+            .unwrap()
+            .unwrap_position();
+        let (width, height) = obj.handle(ObjectCommand::GetSize()).unwrap().unwrap_size();
+        for row in y..y + height {
+            for column in x..x + width {
+                if let Some(cell) = buffer.get_mut(column, row) {
+                    *cell = Default::default();
+                }
+            }
+        }
+        obj.display(&mut buffer, &theme)?;
+        self.flush_buffer(buffer)
+    }
+
+    /// Whether a stack around `handle` shows a layer above the one it is on.
+    fn is_covered(&self, handle: &Handle) -> bool {
+        let mut current: &dyn Object = &*self.objects;
+        for &index in &handle.indexes {
+            if let Some(stack) = current.as_any().downcast_ref::<StackObject>() {
+                if stack.visible.iter().skip(index + 1).any(|&visible| visible) {
+                    return true;
                 }
-                self.changed = false;
             }
+            current = match current.child(index) {
+                Some(child) => child,
+                None => return false,
+            };
         }
+        false
+    }
+
+    /// Sets the value of the object at `handle` and redraws only that object. A container's
+    /// value can change what is laid out inside it, e.g. the active tab, so that redraws
+    /// everything.
+    pub fn set_value(&mut self, handle: &Handle, value: Value) -> io::Result<()> {
+        let obj = self.with(handle);
+        obj.handle(ObjectCommand::SetValue(value)).map_err(|_| {
+            io::Error::new(io::ErrorKind::InvalidInput, "object rejected the value")
+        })?;
+        if obj.is_container() {
+            self.changed = true;
+            self.keep_selection_visible();
+        }
+        self.redraw_object(handle)
+    }
+
+    pub fn set_theme(&mut self, theme: Theme) {
+        self.theme = theme;
+        self.changed = true;
+    }
+
+    pub fn backend(&self) -> &dyn Backend {
+        &*self.backend
+    }
+
+    pub fn backend_mut(&mut self) -> &mut dyn Backend {
+        &mut *self.backend
     }
 
     pub fn with(&mut self, handle: &Handle) -> &mut dyn Object {
         let mut current: &mut dyn Object = &mut *self.objects;
         for index in handle.indexes.clone() {
-            let container = current
-                .as_any_mut()
-                .downcast_mut::<ContainerObject>()
-                .unwrap();
-            current = &mut *container.content[index];
+            current = current.child_mut(index).unwrap();
         }
         current
     }
 
+    /// Whether the object at `handle` and everything above it is shown, i.e. it isn't on
+    /// a hidden stack layer or an inactive tab.
+    pub fn is_visible(&self, handle: &Handle) -> bool {
+        let mut current: &dyn Object = &*self.objects;
+        for &index in &handle.indexes {
+            if !current.child_visible(index) {
+                return false;
+            }
+            current = match current.child(index) {
+                Some(child) => child,
+                None => return false,
+            };
+        }
+        true
+    }
+
+    fn selectable_at(&mut self, handle: &Handle) -> bool {
+        self.is_visible(handle) && self.with(handle).is_selectable()
+    }
+
+    fn container_at(&mut self, handle: &Handle) -> bool {
+        self.is_visible(handle) && self.with(handle).is_container()
+    }
+
+    /// The topmost, innermost visible object covering cell `(x, y)`.
+    pub fn object_at(&mut self, mut x: usize, mut y: usize) -> Option<Handle> {
+        let mut found: Option<Handle> = None;
+        let mut current = Handle {
+            indexes: Vec::new(),
+        };
+        loop {
+            let count = match self.with(&current).handle(ObjectCommand::GetObjectCount()) {
+                Ok(HandleReturn::ObjectCount(count)) => count,
+                _ => return found,
+            };
+
+            // Later children paint over earlier ones, so check them first
+            let hit = (0..count).rev().find_map(|i| {
+                let mut child = current.clone();
+                child.indexes.push(i);
+                if !self.is_visible(&child) {
+                    return None;
+                }
+                let obj = self.with(&child);
+                let (obj_x, obj_y) = obj
+                    .handle(ObjectCommand::GetPosition())
+                    .ok()?
+                    .unwrap_position();
+                let (width, height) = obj.handle(ObjectCommand::GetSize()).ok()?.unwrap_size();
+                if x >= obj_x && x < obj_x + width && y >= obj_y && y < obj_y + height {
+                    Some(child)
+                } else {
+                    None
+                }
+            });
+
+            match hit {
+                Some(child) => {
+                    let obj = self.with(&child);
+                    if let Some((dx, dy)) = obj.scroll_offset() {
+                        // The scrollbars cover the content under them
+                        let on_bar = obj
+                            .as_any()
+                            .downcast_ref::<ScrollViewObject>()
+                            .is_some_and(|view| !view.shows(x, y));
+                        if on_bar {
+                            return Some(child);
+                        }
+                        x += dx;
+                        y += dy;
+                    }
+                    found = Some(child.clone());
+                    current = child;
+                }
+                None => return found,
+            }
+        }
+    }
+
+    /// How far the scroll views around `handle` have moved it, added up, or `None` if it
+    /// isn't inside one.
+    pub(crate) fn scroll_offset_at(&self, handle: &Handle) -> Option<(usize, usize)> {
+        let mut offset = None;
+        let mut current: &dyn Object = &*self.objects;
+        for &index in &handle.indexes {
+            if let Some((dx, dy)) = current.scroll_offset() {
+                let (x, y) = offset.unwrap_or((0, 0));
+                offset = Some((x + dx, y + dy));
+            }
+            current = current.child(index)?;
+        }
+        offset
+    }
+
+    /// Scrolls every scroll view around the selected object so it can be seen, starting
+    /// with the innermost.
+    fn scroll_to_selected(&mut self) {
+        let mut target = self.selected.clone();
+        let mut scope = self.selected.clone();
+        while scope.indexes.pop().is_some() {
+            if self.with(&scope).scroll_offset().is_none() {
+                continue;
+            }
+            let obj = self.with(&target);
+            let position = obj
+                .handle(ObjectCommand::GetPosition())
+                .unwrap()
+                .unwrap_position();
+            let size = obj.handle(ObjectCommand::GetSize()).unwrap().unwrap_size();
+            self.with(&scope)
+                .handle(ObjectCommand::ScrollIntoView(position, size))
+                .unwrap();
+            // An outer view only has to bring the inner one into view
+            target = scope.clone();
+            self.changed = true;
+        }
+    }
+
+    /// The first selectable object at or inside `handle`, skipping hidden children.
+    fn first_selectable(&mut self, handle: &Handle) -> Option<Handle> {
+        if !handle.indexes.is_empty() && self.selectable_at(handle) {
+            return Some(handle.clone());
+        }
+        if !self.container_at(handle) {
+            return None;
+        }
+        let count = self
+            .with(handle)
+            .handle(ObjectCommand::GetObjectCount())
+            .ok()?
+            .unwrap_count();
+        (0..count).find_map(|i| {
+            let mut child = handle.clone();
+            child.indexes.push(i);
+            self.first_selectable(&child)
+        })
+    }
+
+    /// Moves the selection off an object that was just hidden, e.g. on a tab that is no
+    /// longer active, to the first selectable object in the closest place still shown.
+    pub(crate) fn keep_selection_visible(&mut self) {
+        let selected = self.selected.clone();
+        if selected.indexes.is_empty() || self.is_visible(&selected) {
+            return;
+        }
+
+        let mut scope = selected.clone();
+        scope.indexes.pop();
+        loop {
+            if self.is_visible(&scope) {
+                if let Some(handle) = self.first_selectable(&scope) {
+                    self.set_selected(&handle);
+                    return;
+                }
+            }
+            if scope.indexes.pop().is_none() {
+                break;
+            }
+        }
+
+        self.with(&selected)
+            .handle(ObjectCommand::SetSelected(false))
+            .unwrap();
+        self.selected = Handle {
+            indexes: Vec::new(),
+        };
+    }
+
     pub fn set_selected(&mut self, handle: &Handle) {
         if !self.selected.indexes.is_empty() {
             self.with(&self.selected.clone())
@@ -97,20 +465,90 @@ impl TuiHandler {
             .unwrap();
     }
 
+    /// Shows `dialog` over the layout. Selection movement and keys go to the dialog
+    /// until it is answered or closed, then the focus goes back where it was.
+    pub fn open_dialog(&mut self, dialog: Box<DialogObject>) {
+        let previous = match self.dialog.take() {
+            Some((_, previous)) => previous,
+            None => {
+                if !self.selected.indexes.is_empty() {
+                    self.with(&self.selected.clone())
+                        .handle(ObjectCommand::SetSelected(false))
+                        .unwrap();
+                }
+                self.selected.clone()
+            }
+        };
+        self.dialog = Some((dialog, previous));
+        self.changed = true;
+    }
+
+    /// Takes the dialog down without calling its callback and gives the focus back.
+    pub fn close_dialog(&mut self) -> Option<Box<DialogObject>> {
+        let (dialog, previous) = self.dialog.take()?;
+        self.selected = previous;
+        if !self.selected.indexes.is_empty() {
+            self.with(&self.selected.clone())
+                .handle(ObjectCommand::SetSelected(true))
+                .unwrap();
+        }
+        self.changed = true;
+        Some(dialog)
+    }
+
+    pub fn has_dialog(&self) -> bool {
+        self.dialog.is_some()
+    }
+
+    /// Closes the dialog and reports `choice` to its callback.
+    pub(crate) fn answer_dialog(&mut self, choice: Option<usize>) {
+        if let Some(mut dialog) = self.close_dialog() {
+            if let Some(mut callback) = dialog.callback.take() {
+                callback(self, choice);
+            }
+        }
+    }
+
+    pub(crate) fn dialog_mut(&mut self) -> Option<&mut DialogObject> {
+        self.dialog.as_mut().map(|(dialog, _)| &mut **dialog)
+    }
+
     pub fn add_trigger(&mut self, trigger: Trigger, callback: Box<dyn FnMut(&mut TuiHandler)>) {
         self.triggers.push((trigger, callback));
     }
 
+    /// Restores the terminal right away and stops `run`.
     pub fn exit(&mut self) {
-        disable_raw_mode().unwrap();
-        execute!(io::stdout(), LeaveAlternateScreen).unwrap();
-        execute!(io::stdout(), Show).unwrap();
-        execute!(std::io::stdout(), crossterm::event::DisableMouseCapture).unwrap();
+        self.quit();
+        self.backend.leave().unwrap();
     }
 
     pub fn selectable_movement(&mut self, direction: SelectionDirection) {
+        if let Some(dialog) = self.dialog_mut() {
+            dialog
+                .handle(ObjectCommand::MoveSelection(direction))
+                .unwrap();
+            self.changed = true;
+            return;
+        }
+
+        // Nothing is selected, e.g. after switching to a tab with nothing to select, so
+        // start from the first selectable object if there is one
+        if self.selected.indexes.is_empty() {
+            let root = Handle {
+                indexes: Vec::new(),
+            };
+            if let Some(first) = self.first_selectable(&root) {
+                self.set_selected(&first);
+                self.scroll_to_selected();
+                self.changed = true;
+            }
+            return;
+        }
+
         let mut current_handle = self.selected.clone();
         self.selectable_movement_specific(direction, current_handle);
+        self.scroll_to_selected();
     }
 
     fn container_bound(
@@ -169,7 +607,7 @@ impl TuiHandler {
                     return;
                 }
 
-                if self.with(&current_handle).as_any().is::<ContainerObject>() {
+                if self.container_at(&current_handle) {
                     if self
                         .with(&current_handle)
                         .handle(ObjectCommand::GetObjectCount())
@@ -180,7 +618,7 @@ impl TuiHandler {
                         container_handle = current_handle.clone();
                         container_handle.indexes.push(0);
                         current_handle.indexes.push(0);
-                        if self.with(&current_handle).is_selectable() {
+                        if self.selectable_at(&current_handle) {
                             self.set_selected(&current_handle);
                             return;
                         } else {
@@ -196,27 +634,41 @@ impl TuiHandler {
                 ) {
                     *current_handle.indexes.last_mut().unwrap() += 1;
 
-                    if self.with(&current_handle).is_selectable() {
+                    if self.selectable_at(&current_handle) {
                         self.set_selected(&current_handle);
                         return;
                     } else {
                         self.selectable_movement_specific(direction, current_handle);
                     }
                 } else {
-                    if container_handle.indexes.is_empty() {
-                        // Reached the top-level container and can't go further down
-                        return;
+                    // Climb out until a container has a next sibling to move on to
+                    loop {
+                        if container_handle.indexes.is_empty() {
+                            // Reached the top-level container and can't go further down
+                            return;
+                        }
+                        current_handle = container_handle.clone();
+                        container_handle.indexes.pop();
+                        let count = self
+                            .with(&container_handle)
+                            .handle(ObjectCommand::GetObjectCount())
+                            .unwrap()
+                            .unwrap_count();
+                        if *current_handle.indexes.last().unwrap() + 1 < count {
+                            break;
+                        }
                     }
-
-                    current_handle = container_handle.clone();
                     *current_handle.indexes.last_mut().unwrap() += 1;
-                    container_handle.indexes.pop();
 
-                    self.selectable_movement_specific(direction, current_handle);
+                    if self.selectable_at(&current_handle) {
+                        self.set_selected(&current_handle);
+                    } else {
+                        self.selectable_movement_specific(direction, current_handle);
+                    }
                 }
             }
             SelectionDirection::Up => {
-                if self.with(&current_handle).as_any().is::<ContainerObject>() {
+                if self.container_at(&current_handle) {
                     if self
                         .with(&current_handle)
                         .handle(ObjectCommand::GetObjectCount())
@@ -234,7 +686,7 @@ impl TuiHandler {
                                 - 1,
                         );
 
-                        if self.with(&current_handle).is_selectable() {
+                        if self.selectable_at(&current_handle) {
                             self.set_selected(&current_handle);
                             return;
                         } else {
@@ -251,28 +703,168 @@ impl TuiHandler {
                 ) {
                     *current_handle.indexes.last_mut().unwrap() -= 1;
 
-                    if self.with(&current_handle).is_selectable() {
+                    if self.selectable_at(&current_handle) {
                         self.set_selected(&current_handle);
                         return;
                     } else {
                         self.selectable_movement_specific(direction, current_handle);
                     }
                 } else {
-                    if current_handle.indexes.first() == Some(&0) {
-                        // Reached the top-level container and can't go further up
-                        return;
-                    }
-                    while current_handle.indexes.last() == Some(&0) {
-                        current_handle.indexes.pop();
+                    // Climb out until a container has a previous sibling to move on to
+                    loop {
+                        if container_handle.indexes.is_empty() {
+                            // Reached the top-level container and can't go further up
+                            return;
+                        }
+                        current_handle = container_handle.clone();
+                        container_handle.indexes.pop();
+                        if *current_handle.indexes.last().unwrap() > 0 {
+                            break;
+                        }
                     }
-                    container_handle = current_handle.clone();
-                    container_handle.indexes.pop();
                     *current_handle.indexes.last_mut().unwrap() -= 1;
 
-                    self.selectable_movement_specific(direction, current_handle);
+                    if self.selectable_at(&current_handle) {
+                        self.set_selected(&current_handle);
+                    } else {
+                        self.selectable_movement_specific(direction, current_handle);
+                    }
                 }
             }
             _ => {}
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::backend::test::TestBackend;
+    use crate::objects::button::ButtonObject;
+    use crate::objects::progress::ProgressBarObject;
+    use crate::objects::stack::{LayerPlacement, StackObject};
+    use crate::objects::tabs::TabsObject;
+    use crate::objects::text::TextObject;
+    use crate::objects::{Handle, ObjectCommand, Value};
+    use crate::TuiHandler;
+
+    #[test]
+    fn redrawing_under_a_visible_layer_keeps_the_layer() {
+        let mut tui = TuiHandler::with_backend(Box::new(TestBackend::new(12, 4)));
+        let root = Handle {
+            indexes: Vec::new(),
+        };
+        let stack = tui
+            .with(&root)
+            .handle(ObjectCommand::AddObject(StackObject::new()))
+            .unwrap()
+            .unwrap_handle();
+        let bar = tui
+            .with(&stack)
+            .handle(ObjectCommand::AddLayer(
+                ProgressBarObject::new(),
+                LayerPlacement::Fill,
+            ))
+            .unwrap()
+            .unwrap_handle();
+        tui.with(&stack)
+            .handle(ObjectCommand::AddLayer(
+                TextObject::new("popup"),
+                LayerPlacement::Fill,
+            ))
+            .unwrap();
+        tui.draw().unwrap();
+        let backend = |tui: &TuiHandler| {
+            tui.backend()
+                .as_any()
+                .downcast_ref::<TestBackend>()
+                .unwrap()
+                .lines()
+        };
+        let before = backend(&tui);
+        assert!(before.iter().any(|line| line.contains("popup")));
+
+        tui.set_value(&bar, Value::Ratio(0.5)).unwrap();
+        assert_eq!(backend(&tui), before);
+    }
+
+    #[test]
+    fn switching_tabs_through_set_value_lays_out_the_new_tab() {
+        let mut tui = TuiHandler::with_backend(Box::new(TestBackend::new(20, 5)));
+        let root = Handle {
+            indexes: Vec::new(),
+        };
+        let tabs = tui
+            .with(&root)
+            .handle(ObjectCommand::AddObject(TabsObject::new()))
+            .unwrap()
+            .unwrap_handle();
+        let button = tui
+            .with(&tabs)
+            .handle(ObjectCommand::AddTab(
+                "A".to_string(),
+                ButtonObject::new("first", Box::new(|| {})),
+            ))
+            .unwrap()
+            .unwrap_handle();
+        tui.with(&tabs)
+            .handle(ObjectCommand::AddTab(
+                "B".to_string(),
+                TextObject::new("second"),
+            ))
+            .unwrap();
+        tui.set_selected(&button);
+        tui.draw().unwrap();
+
+        tui.set_value(&tabs, Value::Index(1)).unwrap();
+        let lines = tui
+            .backend()
+            .as_any()
+            .downcast_ref::<TestBackend>()
+            .unwrap()
+            .lines();
+        assert!(
+            lines.iter().any(|line| line.contains("second")),
+            "{lines:?}"
+        );
+        assert!(
+            !lines.iter().any(|line| line.contains("first")),
+            "{lines:?}"
+        );
+        assert!(tui.selected.indexes.is_empty());
+    }
+
+    #[test]
+    fn ticking_moves_an_indeterminate_progress_bar() {
+        let mut tui = TuiHandler::with_backend(Box::new(TestBackend::new(20, 3)));
+        let root = Handle {
+            indexes: Vec::new(),
+        };
+        let bar = tui
+            .with(&root)
+            .handle(ObjectCommand::AddObject(ProgressBarObject::new()))
+            .unwrap()
+            .unwrap_handle();
+        tui.draw().unwrap();
+
+        // A bar that isn't animating doesn't need a redraw
+        tui.tick();
+        assert!(!tui.changed);
+
+        tui.with(&bar)
+            .handle(ObjectCommand::SetIndeterminate(true))
+            .unwrap();
+        tui.draw().unwrap();
+        let lines = |tui: &TuiHandler| {
+            tui.backend()
+                .as_any()
+                .downcast_ref::<TestBackend>()
+                .unwrap()
+                .lines()
+        };
+        let before = lines(&tui);
+        tui.tick();
+        assert!(tui.changed);
+        tui.draw().unwrap();
+        assert_ne!(lines(&tui), before);
+    }
+}
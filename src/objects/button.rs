@@ -1,8 +1,9 @@
-use std::io;
-
-use crossterm::{cursor, execute};
-
+use crate::backend::Backend;
 use crate::objects::*;
+use crate::style::Style;
+use crate::theme::{Role, Theme};
+use crate::wrap::{display_width, truncate};
+use std::io;
 
 pub struct ButtonObject {
     pub text: String,
@@ -10,6 +11,8 @@ pub struct ButtonObject {
     pub callback: Box<dyn FnMut()>,
     pub size: (usize, usize),
     pub position: (usize, usize),
+    pub style: Option<Style>,
+    pub selected_style: Option<Style>,
 }
 
 impl ButtonObject {
@@ -20,37 +23,24 @@ impl ButtonObject {
             callback,
             size: (0, 0),
             position: (0, 0),
+            style: None,
+            selected_style: None,
         })
     }
 }
 
 impl Object for ButtonObject {
-    fn display(&self) {
-        let text_bits: Vec<&str> = self
-            .text
-            .as_bytes()
-            .chunks(self.size.0)
-            .map(|chunk| std::str::from_utf8(chunk).unwrap())
-            .collect();
-
-        let _ = execute!(
-            io::stdout(),
-            cursor::MoveTo(self.position.0 as u16, self.position.1 as u16 as u16),
-        );
+    fn display(&self, backend: &mut dyn Backend, theme: &Theme) -> io::Result<()> {
+        backend.move_to(self.position.0, self.position.1)?;
 
-        if self.is_selected {
-            let _ = execute!(
-                io::stdout(),
-                crossterm::style::SetAttribute(crossterm::style::Attribute::Reverse)
-            );
-        }
+        let style = if self.is_selected {
+            self.selected_style
+                .unwrap_or(theme.style(Role::ButtonSelected))
+        } else {
+            self.style.unwrap_or(theme.style(Role::Button))
+        };
 
-        println!("{}", text_bits[0]);
-        execute!(
-            io::stdout(),
-            crossterm::style::SetAttribute(crossterm::style::Attribute::NoReverse)
-        )
-        .unwrap();
+        backend.print(truncate(&self.text, self.size.0), style)
     }
 
     fn handle(&mut self, command: ObjectCommand) -> Result<HandleReturn, ()> {
@@ -74,10 +64,22 @@ impl Object for ButtonObject {
                 self.is_selected = selected;
                 Ok(HandleReturn::None)
             }
+            ObjectCommand::SetStyle(style) => {
+                self.style = Some(style);
+                Ok(HandleReturn::None)
+            }
+            ObjectCommand::SetSelectedStyle(style) => {
+                self.selected_style = Some(style);
+                Ok(HandleReturn::None)
+            }
             _ => Err(()),
         }
     }
 
+    fn natural_size(&self) -> Option<(usize, usize)> {
+        Some((display_width(&self.text), 1))
+    }
+
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
@@ -0,0 +1,121 @@
+use crate::backend::Backend;
+use crate::objects::*;
+use crate::style::Style;
+use crate::theme::{Role, Theme};
+use crate::wrap::{display_width, truncate};
+use crossterm::event::{KeyCode, MouseButton, MouseEventKind};
+use std::io;
+
+/// `[x] label`, toggled with Enter, Space or a click. The callback gets the new state.
+pub struct CheckboxObject {
+    pub label: String,
+    pub checked: bool,
+    pub is_selected: bool,
+    pub callback: Box<dyn FnMut(bool)>,
+    pub size: (usize, usize),
+    pub position: (usize, usize),
+    pub style: Option<Style>,
+    pub selected_style: Option<Style>,
+}
+
+impl CheckboxObject {
+    pub fn new(label: &str, callback: Box<dyn FnMut(bool)>) -> Box<Self> {
+        Box::new(CheckboxObject {
+            label: label.to_string(),
+            checked: false,
+            is_selected: false,
+            callback,
+            size: (0, 0),
+            position: (0, 0),
+            style: None,
+            selected_style: None,
+        })
+    }
+
+    fn toggle(&mut self) -> Result<HandleReturn, ()> {
+        self.checked = !self.checked;
+        (self.callback)(self.checked);
+        Ok(HandleReturn::None)
+    }
+}
+
+impl Object for CheckboxObject {
+    fn display(&self, backend: &mut dyn Backend, theme: &Theme) -> io::Result<()> {
+        backend.move_to(self.position.0, self.position.1)?;
+
+        let style = if self.is_selected {
+            self.selected_style
+                .unwrap_or(theme.style(Role::ButtonSelected))
+        } else {
+            self.style.unwrap_or(theme.style(Role::Button))
+        };
+
+        let mark = if self.checked { 'x' } else { ' ' };
+        let text = format!("[{}] {}", mark, self.label);
+        backend.print(truncate(&text, self.size.0), style)
+    }
+
+    fn handle(&mut self, command: ObjectCommand) -> Result<HandleReturn, ()> {
+        match command {
+            ObjectCommand::SetSize((width, height)) => {
+                self.size = (width, height);
+                Ok(HandleReturn::None)
+            }
+            ObjectCommand::GetSize() => Ok(HandleReturn::Size(self.size)),
+            ObjectCommand::SetPosition((x, y)) => {
+                self.position = (x, y);
+                Ok(HandleReturn::None)
+            }
+            ObjectCommand::GetPosition() => Ok(HandleReturn::Position(self.position)),
+            ObjectCommand::SetText(label) => {
+                self.label = label;
+                Ok(HandleReturn::None)
+            }
+            ObjectCommand::GetValue() => Ok(HandleReturn::Value(Value::Bool(self.checked))),
+            ObjectCommand::SetValue(Value::Bool(checked)) => {
+                self.checked = checked;
+                Ok(HandleReturn::None)
+            }
+            ObjectCommand::GetSelected() => Ok(HandleReturn::Selected(self.is_selected)),
+            ObjectCommand::SetSelected(selected) => {
+                self.is_selected = selected;
+                Ok(HandleReturn::None)
+            }
+            ObjectCommand::SetStyle(style) => {
+                self.style = Some(style);
+                Ok(HandleReturn::None)
+            }
+            ObjectCommand::SetSelectedStyle(style) => {
+                self.selected_style = Some(style);
+                Ok(HandleReturn::None)
+            }
+            ObjectCommand::KeyPress(key) => match key.code {
+                KeyCode::Enter | KeyCode::Char(' ') => self.toggle(),
+                _ => Err(()),
+            },
+            ObjectCommand::Mouse(mouse) => match mouse.kind {
+                MouseEventKind::Down(MouseButton::Left) => self.toggle(),
+                _ => Err(()),
+            },
+            _ => Err(()),
+        }
+    }
+
+    fn natural_size(&self) -> Option<(usize, usize)> {
+        Some((display_width(&self.label) + 4, 1))
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+
+    fn is_selectable(&self) -> bool {
+        true
+    }
+}
+
+impl Selectable for CheckboxObject {}
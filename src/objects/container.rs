@@ -1,6 +1,11 @@
+use crate::backend::Backend;
+use crate::border::{BorderStyle, Borders};
+use crate::layout::{solve, Constraint, HorizontalAlignment, Sides};
 use crate::objects::{Handle, HandleReturn, Object, ObjectCommand};
-use crossterm::{cursor, execute};
-use std::io::{self};
+use crate::style::Style;
+use crate::theme::{Role, Theme};
+use crate::wrap::{display_width, fit, Overflow};
+use std::io;
 
 #[derive(PartialEq, Clone, Copy)]
 pub enum FlowDirection {
@@ -13,10 +18,19 @@ pub struct ContainerObject {
     pub content: Vec<Box<dyn Object>>,
     pub(crate) size: (usize, usize),
     pub position: (usize, usize),
-    pub(crate) spacing: Vec<usize>,
+    pub(crate) constraints: Vec<Constraint>,
     pub(crate) flow: FlowDirection,
     pub(crate) index: Vec<usize>,
     border: bool,
+    border_style: Option<Style>,
+    border_kind: BorderStyle,
+    borders: Borders,
+    title: Option<(String, HorizontalAlignment)>,
+    footer: Option<(String, HorizontalAlignment)>,
+    padding: Sides,
+    margin: Sides,
+    gap: usize,
+    collapse_borders: bool,
 }
 
 impl ContainerObject {
@@ -25,137 +39,141 @@ impl ContainerObject {
             content: Vec::new(),
             size: (0, 0),
             position: (0, 0),
-            spacing: Vec::new(),
+            constraints: Vec::new(),
             flow: FlowDirection::Row,
             index: Vec::new(),
             border: true,
+            border_style: None,
+            border_kind: BorderStyle::default(),
+            borders: Borders::default(),
+            title: None,
+            footer: None,
+            padding: Sides::default(),
+            margin: Sides::default(),
+            gap: 0,
+            collapse_borders: false,
         })
     }
 
-    pub fn update_sizes(&mut self) {
-        if self.spacing.is_empty() {
-            self.spacing = vec![1];
-        }
-
-        self.spacing
-            .resize(self.content.len(), *self.spacing.last().unwrap());
-
-        let spacing_sum = self.spacing.iter().sum::<usize>();
+    pub fn add_object(&mut self, obj: Box<dyn Object>) {
+        self.content.push(obj);
+    }
 
-        let length = self.content.len();
+    /// The cells taken up by the border on each side.
+    fn border_insets(&self) -> Sides {
+        if self.border && self.border_kind != BorderStyle::None {
+            self.borders.insets()
+        } else {
+            Sides::default()
+        }
+    }
 
-        for (i, obj) in self.content.iter_mut().enumerate() {
-            let spacing = self.spacing[i] as f32 / spacing_sum as f32;
+    fn display_border(&self, backend: &mut dyn Backend, theme: &Theme) -> io::Result<()> {
+        let glyphs = match self.border_kind.glyphs() {
+            Some(glyphs) if self.border => glyphs,
+            _ => return Ok(()),
+        };
+        let (x, y) = self.position;
+        let (width, height) = self.size;
 
-            let border_offset = if self.border { 1 } else { 0 };
+        if width < 2 || height < 2 {
+            return Ok(());
+        }
 
-            let mut size = match self.flow {
-                FlowDirection::Row | FlowDirection::Toggle => {
-                    ((spacing * self.size.0 as f32) as usize, self.size.1)
-                }
-                FlowDirection::Column => (self.size.0, ((self.size.1 as f32) * spacing) as usize),
-            };
+        let style = self.border_style.unwrap_or(theme.style(Role::Border));
+        let sides = self.borders;
+        let horizontal = glyphs.horizontal.repeat(width - 2);
+        let corner = |on: bool, glyph: &'static str| if on { glyph } else { glyphs.horizontal };
 
-            size = (
-                size.0.saturating_sub(border_offset * 2),
-                size.1.saturating_sub(border_offset * 2),
-            );
+        // Draw top border
+        if sides.top {
+            backend.move_to(x, y)?;
+            backend.print_joined(
+                &format!(
+                    "{}{}{}",
+                    corner(sides.left, glyphs.top_left),
+                    horizontal,
+                    corner(sides.right, glyphs.top_right)
+                ),
+                style,
+            )?;
+        }
 
-            if length > 1 && i != length - 1 && self.border {
-                match self.flow {
-                    FlowDirection::Row | FlowDirection::Toggle => size.0 = size.0 + 1,
-                    FlowDirection::Column => size.1 = size.1 + 1,
-                }
+        // Draw side borders
+        let first_row = if sides.top { 1 } else { 0 };
+        let last_row = if sides.bottom { height - 1 } else { height };
+        for row in first_row..last_row {
+            if sides.left {
+                backend.move_to(x, y + row)?;
+                backend.print_joined(glyphs.vertical, style)?;
             }
-
-            let mut position = match self.flow {
-                FlowDirection::Row | FlowDirection::Toggle => {
-                    let x_offset: usize = self.spacing.iter().take(i).sum();
-                    (
-                        self.position.0
-                            + (x_offset * (self.size.0 as f32 / spacing_sum as f32) as usize),
-                        self.position.1,
-                    )
-                }
-                FlowDirection::Column => {
-                    let y_offset: usize = self.spacing.iter().take(i).sum();
-                    (
-                        self.position.0,
-                        self.position.1
-                            + (y_offset * (self.size.1 as f32 / spacing_sum as f32) as usize),
-                    )
-                }
-            };
-
-            position = (position.0 + border_offset, position.1 + border_offset);
-
-            if position.1 + size.1 + 1 + border_offset == self.position.1 + self.size.1 {
-                size.1 = size.1 + 1;
+            if sides.right {
+                backend.move_to(x + width - 1, y + row)?;
+                backend.print_joined(glyphs.vertical, style)?;
             }
+        }
 
-            obj.handle(ObjectCommand::SetSize(size)).unwrap();
-            obj.handle(ObjectCommand::SetPosition(position)).unwrap();
-
-            if obj.as_any().is::<ContainerObject>() {
-                obj.as_any_mut()
-                    .downcast_mut::<ContainerObject>()
-                    .unwrap()
-                    .update_sizes();
-            }
+        // Draw bottom border
+        if sides.bottom {
+            backend.move_to(x, y + height - 1)?;
+            backend.print_joined(
+                &format!(
+                    "{}{}{}",
+                    corner(sides.left, glyphs.bottom_left),
+                    horizontal,
+                    corner(sides.right, glyphs.bottom_right)
+                ),
+                style,
+            )?;
         }
-    }
 
-    pub fn add_object(&mut self, obj: Box<dyn Object>) {
-        self.content.push(obj);
+        Ok(())
     }
 
-    fn display_border(&self) {
-        if self.border {
-            let (x, y) = self.position;
-            let (width, height) = self.size;
+    // Captions go on after the children, which may have drawn their own edges over ours
+    fn display_captions(&self, backend: &mut dyn Backend, theme: &Theme) -> io::Result<()> {
+        if !self.border || self.border_kind.glyphs().is_none() || self.size.1 < 2 {
+            return Ok(());
+        }
 
-            // Draw top border
-            execute!(io::stdout(), cursor::MoveTo(x as u16, y as u16),).unwrap();
-            print!("╭");
-            for _ in 1..width - 1 {
-                print!("─");
-            }
-            print!("╮");
+        let title_style = theme.style(Role::Title);
+        if let (true, Some((title, alignment))) = (self.borders.top, &self.title) {
+            self.display_caption(backend, title, *alignment, self.position.1, title_style)?;
+        }
+        if let (true, Some((footer, alignment))) = (self.borders.bottom, &self.footer) {
+            let y = self.position.1 + self.size.1 - 1;
+            self.display_caption(backend, footer, *alignment, y, title_style)?;
+        }
+        Ok(())
+    }
 
-            // Draw side borders
-            for row in 1..height - 1 {
-                execute!(io::stdout(), cursor::MoveTo(x as u16, (y + row) as u16),).unwrap();
-                print!("│");
-                execute!(
-                    io::stdout(),
-                    cursor::MoveTo((x + width - 1) as u16, (y + row) as u16),
-                )
-                .unwrap();
-                print!("│");
-            }
+    /// Draws `text` into the horizontal edge at row `y`, keeping clear of the corners.
+    fn display_caption(
+        &self,
+        backend: &mut dyn Backend,
+        text: &str,
+        alignment: HorizontalAlignment,
+        y: usize,
+        style: Style,
+    ) -> io::Result<()> {
+        let available = self.size.0.saturating_sub(2);
+        let caption = fit(&format!(" {} ", text), available, Overflow::Ellipsis);
+        let offset = alignment.offset(available, display_width(&caption));
 
-            // Draw bottom border
-            execute!(
-                io::stdout(),
-                cursor::MoveTo(x as u16, (y + height - 1) as u16),
-            )
-            .unwrap();
-            print!("╰");
-            for _ in 1..width - 1 {
-                print!("─");
-            }
-            print!("╯");
-        }
+        backend.move_to(self.position.0 + 1 + offset, y)?;
+        backend.print(&caption, style)
     }
 }
 
 impl Object for ContainerObject {
-    fn display(&self) {
-        self.display_border();
+    fn display(&self, backend: &mut dyn Backend, theme: &Theme) -> io::Result<()> {
+        self.display_border(backend, theme)?;
 
         for obj in &self.content {
-            obj.display();
+            obj.display(backend, theme)?;
         }
+
+        self.display_captions(backend, theme)
     }
 
     fn handle(&mut self, command: ObjectCommand) -> Result<HandleReturn, ()> {
@@ -171,7 +189,11 @@ impl Object for ContainerObject {
             }
             ObjectCommand::GetPosition() => Ok(HandleReturn::Position(self.position)),
             ObjectCommand::SetSpacing(spacing) => {
-                self.spacing = spacing;
+                self.constraints = spacing.into_iter().map(Constraint::Fill).collect();
+                Ok(HandleReturn::None)
+            }
+            ObjectCommand::SetConstraints(constraints) => {
+                self.constraints = constraints;
                 Ok(HandleReturn::None)
             }
             ObjectCommand::SetFlow(flow) => {
@@ -191,6 +213,42 @@ impl Object for ContainerObject {
                 self.border = border;
                 Ok(HandleReturn::None)
             }
+            ObjectCommand::SetStyle(style) => {
+                self.border_style = Some(style);
+                Ok(HandleReturn::None)
+            }
+            ObjectCommand::SetBorderStyle(kind) => {
+                self.border_kind = kind;
+                Ok(HandleReturn::None)
+            }
+            ObjectCommand::SetBorders(borders) => {
+                self.borders = borders;
+                Ok(HandleReturn::None)
+            }
+            ObjectCommand::SetTitle(title, alignment) => {
+                self.title = Some((title, alignment));
+                Ok(HandleReturn::None)
+            }
+            ObjectCommand::SetFooter(footer, alignment) => {
+                self.footer = Some((footer, alignment));
+                Ok(HandleReturn::None)
+            }
+            ObjectCommand::SetPadding(padding) => {
+                self.padding = padding;
+                Ok(HandleReturn::None)
+            }
+            ObjectCommand::SetMargin(margin) => {
+                self.margin = margin;
+                Ok(HandleReturn::None)
+            }
+            ObjectCommand::SetGap(gap) => {
+                self.gap = gap;
+                Ok(HandleReturn::None)
+            }
+            ObjectCommand::SetCollapsedBorders(collapse) => {
+                self.collapse_borders = collapse;
+                Ok(HandleReturn::None)
+            }
             ObjectCommand::AddObject(mut obj) => {
                 let mut indexes = self.index.clone();
                 indexes.push(self.content.len());
@@ -218,4 +276,227 @@ impl Object for ContainerObject {
     fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
         self
     }
+
+    fn is_container(&self) -> bool {
+        true
+    }
+
+    fn child(&self, index: usize) -> Option<&dyn Object> {
+        self.content.get(index).map(|obj| &**obj)
+    }
+
+    fn child_mut(&mut self, index: usize) -> Option<&mut dyn Object> {
+        match self.content.get_mut(index) {
+            Some(obj) => Some(&mut **obj),
+            None => None,
+        }
+    }
+
+    /// Children at their natural sizes, or their `Length` where one is set, laid end to
+    /// end with the gaps, margins, padding and border around them.
+    fn natural_size(&self) -> Option<(usize, usize)> {
+        let row = self.flow != FlowDirection::Column;
+        let (mut along, mut across) = (0, 0);
+        for (i, obj) in self.content.iter().enumerate() {
+            let (width, height) = obj.natural_size().unwrap_or((1, 1));
+            let (mut extent, mut thickness) = if row {
+                (width, height)
+            } else {
+                (height, width)
+            };
+            match self.constraints.get(i).or(self.constraints.last()) {
+                Some(Constraint::Length(length)) => extent = *length,
+                Some(Constraint::Min(min)) => extent = extent.max(*min),
+                _ => {}
+            }
+            if !self.collapse_borders {
+                let margin = &self.margin;
+                let (before, after) = if row {
+                    (margin.left + margin.right, margin.top + margin.bottom)
+                } else {
+                    (margin.top + margin.bottom, margin.left + margin.right)
+                };
+                extent += before;
+                thickness += after;
+            }
+            along += extent;
+            across = across.max(thickness);
+        }
+
+        let joins = self.content.len().saturating_sub(1);
+        // Collapsed children overlap their neighbours by a cell
+        let along = if self.collapse_borders {
+            along.saturating_sub(joins)
+        } else {
+            along + self.gap * joins
+        };
+        let (mut width, mut height) = if row {
+            (along, across)
+        } else {
+            (across, along)
+        };
+
+        let insets = if self.collapse_borders {
+            Sides::default()
+        } else {
+            self.border_insets()
+        };
+        for sides in [insets, self.padding] {
+            width += sides.left + sides.right;
+            height += sides.top + sides.bottom;
+        }
+        Some((width, height))
+    }
+
+    fn update_sizes(&mut self) {
+        if self.constraints.is_empty() {
+            self.constraints = vec![Constraint::Fill(1)];
+        }
+
+        self.constraints
+            .resize(self.content.len(), *self.constraints.last().unwrap());
+
+        // Children tile the area inside the border and padding exactly, one after another,
+        // with `gap` cells between them. Collapsed borders instead lay children over the
+        // border and overlap neighbours by a cell, so adjacent edges are drawn only once.
+        let (inner_position, inner_size) = if self.collapse_borders {
+            (self.position, self.size)
+        } else {
+            self.border_insets().shrink(self.position, self.size)
+        };
+        let (inner_position, inner_size) = self.padding.shrink(inner_position, inner_size);
+
+        let (gap, overlap) = if self.collapse_borders {
+            (0, 1)
+        } else {
+            (self.gap, 0)
+        };
+        let joins = self.content.len().saturating_sub(1);
+        let total = match self.flow {
+            FlowDirection::Row | FlowDirection::Toggle => inner_size.0,
+            FlowDirection::Column => inner_size.1,
+        };
+        let extents = solve(
+            (total + overlap * joins).saturating_sub(gap * joins),
+            &self.constraints,
+        );
+
+        let mut offset = 0;
+
+        for (i, obj) in self.content.iter_mut().enumerate() {
+            let (position, size) = match self.flow {
+                FlowDirection::Row | FlowDirection::Toggle => (
+                    (inner_position.0 + offset, inner_position.1),
+                    (extents[i], inner_size.1),
+                ),
+                FlowDirection::Column => (
+                    (inner_position.0, inner_position.1 + offset),
+                    (inner_size.0, extents[i]),
+                ),
+            };
+            offset = (offset + extents[i] + gap).saturating_sub(overlap);
+
+            let (position, size) = if self.collapse_borders {
+                (position, size)
+            } else {
+                self.margin.shrink(position, size)
+            };
+
+            obj.handle(ObjectCommand::SetSize(size)).unwrap();
+            obj.handle(ObjectCommand::SetPosition(position)).unwrap();
+
+            obj.update_sizes();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ContainerObject, FlowDirection};
+    use crate::layout::Constraint;
+    use crate::objects::text::TextObject;
+    use crate::objects::{Object, ObjectCommand};
+
+    /// Lays out `count` children and returns each one's start and length along the flow.
+    fn spans(
+        flow: FlowDirection,
+        size: (usize, usize),
+        constraints: Vec<Constraint>,
+        count: usize,
+    ) -> Vec<(usize, usize)> {
+        let mut container = ContainerObject::new();
+        container.flow = flow;
+        container.position = (3, 2);
+        container.size = size;
+        container.constraints = constraints;
+        for _ in 0..count {
+            container.add_object(TextObject::new(""));
+        }
+        container.update_sizes();
+
+        container
+            .content
+            .iter_mut()
+            .map(|obj| {
+                let position = obj
+                    .handle(ObjectCommand::GetPosition())
+                    .unwrap()
+                    .unwrap_position();
+                let size = obj.handle(ObjectCommand::GetSize()).unwrap().unwrap_size();
+                match flow {
+                    FlowDirection::Column => (position.1, size.1),
+                    _ => (position.0, size.0),
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn children_tile_the_inside_without_gaps_or_overlaps() {
+        let constraint_sets = [
+            vec![Constraint::Fill(1)],
+            vec![
+                Constraint::Fill(1),
+                Constraint::Fill(2),
+                Constraint::Fill(3),
+            ],
+            vec![
+                Constraint::Length(2),
+                Constraint::Fill(1),
+                Constraint::Min(1),
+            ],
+            vec![
+                Constraint::Percentage(25),
+                Constraint::Max(3),
+                Constraint::Fill(1),
+            ],
+        ];
+        for flow in [FlowDirection::Row, FlowDirection::Column] {
+            for extent in 2..=40 {
+                for constraints in &constraint_sets {
+                    // Fewer children would cut off the trailing `Fill`, and without
+                    // one the space after the last child is left empty
+                    for count in constraints.len()..=5 {
+                        let size = match flow {
+                            FlowDirection::Column => (10, extent),
+                            _ => (extent, 10),
+                        };
+                        let start = match flow {
+                            FlowDirection::Column => 2,
+                            _ => 3,
+                        };
+                        let spans = spans(flow, size, constraints.clone(), count);
+
+                        // One cell of border on each side
+                        let mut next = start + 1;
+                        for &(position, length) in &spans {
+                            assert_eq!(position, next, "{extent} {constraints:?} {spans:?}");
+                            next = position + length;
+                        }
+                        assert_eq!(next, start + extent - 1, "{extent} {constraints:?}");
+                    }
+                }
+            }
+        }
+    }
 }
@@ -0,0 +1,240 @@
+use crate::backend::Backend;
+use crate::layout::{Constraint, HorizontalAlignment, Sides};
+use crate::objects::button::ButtonObject;
+use crate::objects::container::{ContainerObject, FlowDirection};
+use crate::objects::text::TextObject;
+use crate::objects::{HandleReturn, Object, ObjectCommand, SelectionDirection};
+use crate::style::Style;
+use crate::theme::Theme;
+use crate::wrap::{display_width, wrap, WrapMode};
+use crate::TuiHandler;
+use std::io;
+
+/// Called with the index of the chosen button, or `None` when the dialog was cancelled.
+pub type DialogCallback = Box<dyn FnMut(&mut TuiHandler, Option<usize>)>;
+
+const BUTTON_GAP: usize = 2;
+
+/// A bordered box with a message and a row of buttons, centered over the rest of the
+/// layout. Open it with `TuiHandler::open_dialog`; while it's up it keeps the focus.
+pub struct DialogObject {
+    frame: Box<ContainerObject>,
+    message: String,
+    title: String,
+    buttons: Vec<String>,
+    pub focused: usize,
+    pub(crate) callback: Option<DialogCallback>,
+    size: (usize, usize),
+    position: (usize, usize),
+}
+
+impl DialogObject {
+    pub fn new(
+        title: &str,
+        message: &str,
+        buttons: &[&str],
+        callback: DialogCallback,
+    ) -> Box<Self> {
+        let mut frame = ContainerObject::new();
+        frame.flow = FlowDirection::Column;
+        frame.constraints = vec![Constraint::Fill(1), Constraint::Length(1)];
+        frame
+            .handle(ObjectCommand::SetTitle(
+                title.to_string(),
+                HorizontalAlignment::Center,
+            ))
+            .unwrap();
+        frame
+            .handle(ObjectCommand::SetPadding(Sides::symmetric(0, 1)))
+            .unwrap();
+        frame.handle(ObjectCommand::SetGap(1)).unwrap();
+
+        let mut text = TextObject::new(message);
+        text.wrap = WrapMode::Word;
+        text.alignment = HorizontalAlignment::Center;
+        frame.add_object(text);
+
+        // Fill spacers on either side keep the buttons centered
+        let mut row = ContainerObject::new();
+        row.handle(ObjectCommand::SetBorder(false)).unwrap();
+        row.handle(ObjectCommand::SetGap(BUTTON_GAP)).unwrap();
+        let mut constraints = vec![Constraint::Fill(1)];
+        row.add_object(TextObject::new(""));
+        for label in buttons {
+            constraints.push(Constraint::Length(display_width(label) + 2));
+            row.add_object(ButtonObject::new(&format!(" {} ", label), Box::new(|| {})));
+        }
+        constraints.push(Constraint::Fill(1));
+        row.add_object(TextObject::new(""));
+        row.constraints = constraints;
+        frame.add_object(row);
+
+        let mut dialog = Box::new(DialogObject {
+            frame,
+            message: message.to_string(),
+            title: title.to_string(),
+            buttons: buttons.iter().map(|label| label.to_string()).collect(),
+            focused: 0,
+            callback: Some(callback),
+            size: (0, 0),
+            position: (0, 0),
+        });
+        dialog.focus(0);
+        dialog
+    }
+
+    /// A message with a single OK button.
+    pub fn ok(title: &str, message: &str, callback: DialogCallback) -> Box<Self> {
+        Self::new(title, message, &["OK"], callback)
+    }
+
+    /// OK is button 0, Cancel is button 1.
+    pub fn ok_cancel(title: &str, message: &str, callback: DialogCallback) -> Box<Self> {
+        Self::new(title, message, &["OK", "Cancel"], callback)
+    }
+
+    /// Yes is button 0, No is button 1.
+    pub fn yes_no(title: &str, message: &str, callback: DialogCallback) -> Box<Self> {
+        Self::new(title, message, &["Yes", "No"], callback)
+    }
+
+    fn button_mut(&mut self, index: usize) -> Option<&mut dyn Object> {
+        // Button `i` sits after the leading spacer in the button row
+        self.frame.child_mut(1)?.child_mut(index + 1)
+    }
+
+    fn focus(&mut self, index: usize) {
+        if self.buttons.is_empty() {
+            return;
+        }
+        let previous = self.focused;
+        if let Some(button) = self.button_mut(previous) {
+            button.handle(ObjectCommand::SetSelected(false)).unwrap();
+        }
+        self.focused = index.min(self.buttons.len() - 1);
+        let focused = self.focused;
+        if let Some(button) = self.button_mut(focused) {
+            button.handle(ObjectCommand::SetSelected(true)).unwrap();
+        }
+    }
+
+    /// Which button, if any, covers cell `(x, y)`.
+    pub fn button_at(&mut self, x: usize, y: usize) -> Option<usize> {
+        (0..self.buttons.len()).find(|&i| {
+            let button = match self.button_mut(i) {
+                Some(button) => button,
+                None => return false,
+            };
+            let (bx, by) = button
+                .handle(ObjectCommand::GetPosition())
+                .unwrap()
+                .unwrap_position();
+            let (width, height) = button
+                .handle(ObjectCommand::GetSize())
+                .unwrap()
+                .unwrap_size();
+            x >= bx && x < bx + width && y >= by && y < by + height
+        })
+    }
+
+    /// The box size that fits the message, title and buttons, capped at `available`.
+    fn frame_size(&self, available: (usize, usize)) -> (usize, usize) {
+        // Border plus one cell of padding on each side
+        let chrome = 4;
+        let message_width = self.message.lines().map(display_width).max().unwrap_or(0);
+        let buttons_width = self
+            .buttons
+            .iter()
+            .map(|label| display_width(label) + 2)
+            .sum::<usize>()
+            + BUTTON_GAP * self.buttons.len().saturating_sub(1);
+        let title_width = display_width(&self.title) + 2;
+
+        let width = (message_width.max(buttons_width).max(title_width) + chrome).min(available.0);
+        let lines = wrap(&self.message, width.saturating_sub(chrome), WrapMode::Word)
+            .len()
+            .max(1);
+        // Message, a blank row, the buttons and the top and bottom border
+        let height = (lines + 4).min(available.1);
+        (width, height)
+    }
+}
+
+impl Object for DialogObject {
+    fn display(&self, backend: &mut dyn Backend, theme: &Theme) -> io::Result<()> {
+        let (x, y) = self.frame.position;
+        let (width, height) = self.frame.size;
+        let blank = " ".repeat(width);
+        for row in 0..height {
+            backend.move_to(x, y + row)?;
+            backend.print(&blank, Style::default())?;
+        }
+
+        self.frame.display(backend, theme)
+    }
+
+    fn handle(&mut self, command: ObjectCommand) -> Result<HandleReturn, ()> {
+        match command {
+            ObjectCommand::SetSize((width, height)) => {
+                self.size = (width, height);
+                Ok(HandleReturn::None)
+            }
+            ObjectCommand::GetSize() => Ok(HandleReturn::Size(self.size)),
+            ObjectCommand::SetPosition((x, y)) => {
+                self.position = (x, y);
+                Ok(HandleReturn::None)
+            }
+            ObjectCommand::GetPosition() => Ok(HandleReturn::Position(self.position)),
+            ObjectCommand::SetText(message) => {
+                self.message = message.clone();
+                self.frame
+                    .child_mut(0)
+                    .unwrap()
+                    .handle(ObjectCommand::SetText(message))
+            }
+            ObjectCommand::SetTitle(title, alignment) => {
+                self.title = title.clone();
+                self.frame.handle(ObjectCommand::SetTitle(title, alignment))
+            }
+            command @ (ObjectCommand::SetStyle(_) | ObjectCommand::SetBorderStyle(_)) => {
+                self.frame.handle(command)
+            }
+            ObjectCommand::MoveSelection(direction) => {
+                let count = self.buttons.len();
+                if count == 0 {
+                    return Ok(HandleReturn::None);
+                }
+                let next = match direction {
+                    SelectionDirection::Left | SelectionDirection::Up => {
+                        (self.focused + count - 1) % count
+                    }
+                    SelectionDirection::Right | SelectionDirection::Down => {
+                        (self.focused + 1) % count
+                    }
+                };
+                self.focus(next);
+                Ok(HandleReturn::None)
+            }
+            _ => Err(()),
+        }
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+
+    /// Centers the box inside the area the dialog was given, then lays out its contents.
+    fn update_sizes(&mut self) {
+        let (width, height) = self.frame_size(self.size);
+        self.frame.size = (width, height);
+        self.frame.position = (
+            self.position.0 + (self.size.0 - width) / 2,
+            self.position.1 + (self.size.1 - height) / 2,
+        );
+        self.frame.update_sizes();
+    }
+}
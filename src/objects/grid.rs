@@ -0,0 +1,251 @@
+use crate::backend::Backend;
+use crate::layout::{solve, Constraint};
+use crate::objects::{Handle, HandleReturn, Object, ObjectCommand};
+use crate::theme::Theme;
+use std::io;
+
+/// Where a child sits in a grid, in tracks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GridPlacement {
+    pub row: usize,
+    pub column: usize,
+    pub row_span: usize,
+    pub column_span: usize,
+}
+
+impl GridPlacement {
+    pub fn new(row: usize, column: usize) -> Self {
+        GridPlacement {
+            row,
+            column,
+            row_span: 1,
+            column_span: 1,
+        }
+    }
+
+    pub fn span(mut self, rows: usize, columns: usize) -> Self {
+        self.row_span = rows.max(1);
+        self.column_span = columns.max(1);
+        self
+    }
+}
+
+/// Lays children out on row and column tracks. Children added with `AddObject` fill the
+/// next free cell in reading order; `PlaceObject` puts them somewhere specific.
+pub struct GridObject {
+    pub content: Vec<Box<dyn Object>>,
+    pub placements: Vec<GridPlacement>,
+    pub(crate) size: (usize, usize),
+    pub position: (usize, usize),
+    pub(crate) rows: Vec<Constraint>,
+    pub(crate) columns: Vec<Constraint>,
+    pub(crate) index: Vec<usize>,
+    gap: usize,
+}
+
+impl GridObject {
+    pub fn new(rows: Vec<Constraint>, columns: Vec<Constraint>) -> Box<Self> {
+        Box::new(GridObject {
+            content: Vec::new(),
+            placements: Vec::new(),
+            size: (0, 0),
+            position: (0, 0),
+            rows,
+            columns,
+            index: Vec::new(),
+            gap: 0,
+        })
+    }
+
+    pub fn place_object(&mut self, obj: Box<dyn Object>, placement: GridPlacement) {
+        // The fields are public, so a placement may not have come through `span`
+        let placement = placement.span(placement.row_span, placement.column_span);
+        self.content.push(obj);
+        self.placements.push(placement);
+    }
+
+    /// The first cell, in reading order, that no child covers yet.
+    fn next_free_cell(&self) -> GridPlacement {
+        let columns = self.columns.len().max(1);
+        let mut cell = 0;
+        loop {
+            let (row, column) = (cell / columns, cell % columns);
+            let taken = self.placements.iter().any(|p| {
+                (p.row..p.row + p.row_span).contains(&row)
+                    && (p.column..p.column + p.column_span).contains(&column)
+            });
+            if !taken {
+                return GridPlacement::new(row, column);
+            }
+            cell += 1;
+        }
+    }
+
+    fn add(&mut self, mut obj: Box<dyn Object>, placement: GridPlacement) -> HandleReturn {
+        let mut indexes = self.index.clone();
+        indexes.push(self.content.len());
+        let _ = obj.handle(ObjectCommand::SetIndexes(indexes.clone()));
+        self.place_object(obj, placement);
+        HandleReturn::ObjectHandle(Handle { indexes })
+    }
+}
+
+/// Start offset of every track plus the extent of each, with `gap` cells between tracks.
+fn tracks(total: usize, constraints: &[Constraint], gap: usize) -> (Vec<usize>, Vec<usize>) {
+    let gaps = gap * constraints.len().saturating_sub(1);
+    let extents = solve(total.saturating_sub(gaps), constraints);
+    let mut offsets = Vec::with_capacity(extents.len());
+    let mut offset = 0;
+    for extent in &extents {
+        offsets.push(offset);
+        offset += extent + gap;
+    }
+    (offsets, extents)
+}
+
+/// Start and extent of a run of `span` tracks beginning at `first`, gaps included.
+fn span_of(offsets: &[usize], extents: &[usize], first: usize, span: usize) -> (usize, usize) {
+    if first >= extents.len() {
+        return (0, 0);
+    }
+    let last = (first + span.max(1)).min(extents.len()) - 1;
+    (
+        offsets[first],
+        offsets[last] + extents[last] - offsets[first],
+    )
+}
+
+impl Object for GridObject {
+    fn display(&self, backend: &mut dyn Backend, theme: &Theme) -> io::Result<()> {
+        for obj in &self.content {
+            obj.display(backend, theme)?;
+        }
+        Ok(())
+    }
+
+    fn handle(&mut self, command: ObjectCommand) -> Result<HandleReturn, ()> {
+        match command {
+            ObjectCommand::SetSize((width, height)) => {
+                self.size = (width, height);
+                Ok(HandleReturn::None)
+            }
+            ObjectCommand::GetSize() => Ok(HandleReturn::Size(self.size)),
+            ObjectCommand::SetPosition((x, y)) => {
+                self.position = (x, y);
+                Ok(HandleReturn::None)
+            }
+            ObjectCommand::GetPosition() => Ok(HandleReturn::Position(self.position)),
+            ObjectCommand::SetRows(rows) => {
+                self.rows = rows;
+                Ok(HandleReturn::None)
+            }
+            ObjectCommand::SetColumns(columns) => {
+                self.columns = columns;
+                Ok(HandleReturn::None)
+            }
+            ObjectCommand::SetGap(gap) => {
+                self.gap = gap;
+                Ok(HandleReturn::None)
+            }
+            ObjectCommand::AddObject(obj) => {
+                let placement = self.next_free_cell();
+                Ok(self.add(obj, placement))
+            }
+            ObjectCommand::PlaceObject(obj, placement) => Ok(self.add(obj, placement)),
+            ObjectCommand::GetObjects(callback) => {
+                callback(&self.content);
+                Ok(HandleReturn::None)
+            }
+            ObjectCommand::SetIndexes(indexes) => {
+                self.index = indexes;
+                Ok(HandleReturn::None)
+            }
+            ObjectCommand::GetObjectCount() => Ok(HandleReturn::ObjectCount(self.content.len())),
+            _ => Err(()),
+        }
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+
+    fn is_container(&self) -> bool {
+        true
+    }
+
+    fn child(&self, index: usize) -> Option<&dyn Object> {
+        self.content.get(index).map(|obj| &**obj)
+    }
+
+    fn child_mut(&mut self, index: usize) -> Option<&mut dyn Object> {
+        match self.content.get_mut(index) {
+            Some(obj) => Some(&mut **obj),
+            None => None,
+        }
+    }
+
+    fn update_sizes(&mut self) {
+        let (row_offsets, row_extents) = tracks(self.size.1, &self.rows, self.gap);
+        let (column_offsets, column_extents) = tracks(self.size.0, &self.columns, self.gap);
+
+        for (obj, placement) in self.content.iter_mut().zip(&self.placements) {
+            let (y, height) = span_of(
+                &row_offsets,
+                &row_extents,
+                placement.row,
+                placement.row_span,
+            );
+            let (x, width) = span_of(
+                &column_offsets,
+                &column_extents,
+                placement.column,
+                placement.column_span,
+            );
+
+            obj.handle(ObjectCommand::SetSize((width, height))).unwrap();
+            obj.handle(ObjectCommand::SetPosition((
+                self.position.0 + x,
+                self.position.1 + y,
+            )))
+            .unwrap();
+            obj.update_sizes();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{GridObject, GridPlacement};
+    use crate::layout::Constraint;
+    use crate::objects::text::TextObject;
+    use crate::objects::{Object, ObjectCommand};
+
+    #[test]
+    fn zero_spans_cover_one_cell() {
+        let tracks = vec![Constraint::Fill(1); 2];
+        let mut grid = GridObject::new(tracks.clone(), tracks);
+        grid.size = (10, 4);
+        let placement = GridPlacement {
+            row: 0,
+            column: 0,
+            row_span: 0,
+            column_span: 0,
+        };
+        grid.handle(ObjectCommand::PlaceObject(TextObject::new("a"), placement))
+            .unwrap();
+        // Set directly, skipping the clamp in `place_object`
+        grid.placements.push(placement);
+        grid.content.push(TextObject::new("b"));
+        grid.update_sizes();
+
+        for obj in &mut grid.content {
+            let size = obj.handle(ObjectCommand::GetSize()).unwrap().unwrap_size();
+            assert_eq!(size, (5, 2));
+        }
+        assert_eq!(grid.placements[0], GridPlacement::new(0, 0));
+    }
+}
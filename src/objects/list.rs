@@ -0,0 +1,264 @@
+use crate::backend::Backend;
+use crate::objects::*;
+use crate::style::Style;
+use crate::theme::{Role, Theme};
+use crate::wrap::{display_width, fit, Overflow};
+use crossterm::event::{KeyCode, MouseButton, MouseEventKind};
+use std::collections::BTreeSet;
+use std::io;
+
+/// Where a `ListObject` gets its rows. The list only asks for the rows it is about to
+/// draw, so a source can page them in from anywhere instead of holding them all.
+pub trait ListSource {
+    fn len(&self) -> usize;
+
+    fn row(&self, index: usize) -> String;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl ListSource for Vec<String> {
+    fn len(&self) -> usize {
+        Vec::len(self)
+    }
+
+    fn row(&self, index: usize) -> String {
+        self[index].clone()
+    }
+}
+
+/// Rows the mouse wheel scrolls per notch.
+const WHEEL_STEP: usize = 3;
+
+/// A scrolling list of lines with a cursor row. While selected, the arrow keys, PageUp,
+/// PageDown, Home and End move the cursor and Enter activates it; with multi-select on,
+/// Space marks rows. Clicking a row moves the cursor there, clicking it again activates.
+pub struct ListObject {
+    pub source: Box<dyn ListSource>,
+    pub cursor: usize,
+    /// First row shown.
+    pub scroll: usize,
+    pub multi_select: bool,
+    pub marked: BTreeSet<usize>,
+    pub is_selected: bool,
+    /// Called with the cursor row when it is activated.
+    pub callback: Box<dyn FnMut(usize)>,
+    pub size: (usize, usize),
+    pub position: (usize, usize),
+    pub style: Option<Style>,
+    pub selected_style: Option<Style>,
+}
+
+impl ListObject {
+    pub fn new(items: Vec<String>, callback: Box<dyn FnMut(usize)>) -> Box<Self> {
+        Self::with_source(Box::new(items), callback)
+    }
+
+    pub fn with_source(source: Box<dyn ListSource>, callback: Box<dyn FnMut(usize)>) -> Box<Self> {
+        Box::new(ListObject {
+            source,
+            cursor: 0,
+            scroll: 0,
+            multi_select: false,
+            marked: BTreeSet::new(),
+            is_selected: false,
+            callback,
+            size: (0, 0),
+            position: (0, 0),
+            style: None,
+            selected_style: None,
+        })
+    }
+
+    fn len(&self) -> usize {
+        self.source.len()
+    }
+
+    fn set_cursor(&mut self, cursor: usize) {
+        self.cursor = cursor.min(self.len().saturating_sub(1));
+        let height = self.size.1.max(1);
+        if self.cursor < self.scroll {
+            self.scroll = self.cursor;
+        } else if self.cursor >= self.scroll + height {
+            self.scroll = self.cursor + 1 - height;
+        }
+    }
+
+    fn scroll_by(&mut self, delta: isize) {
+        let max = self.len().saturating_sub(self.size.1);
+        self.scroll = self.scroll.saturating_add_signed(delta).min(max);
+    }
+
+    fn activate(&mut self) -> Result<HandleReturn, ()> {
+        if self.cursor < self.len() {
+            (self.callback)(self.cursor);
+        }
+        Ok(HandleReturn::None)
+    }
+
+    fn toggle_mark(&mut self) {
+        if !self.marked.remove(&self.cursor) && self.cursor < self.len() {
+            self.marked.insert(self.cursor);
+        }
+    }
+
+    fn handle_key(&mut self, code: KeyCode) -> Result<HandleReturn, ()> {
+        let page = self.size.1.max(1);
+        let last = self.len().saturating_sub(1);
+        match code {
+            // Running off either end lets the selection move on from the list
+            KeyCode::Up if self.cursor > 0 => self.set_cursor(self.cursor - 1),
+            KeyCode::Down if self.cursor < last => self.set_cursor(self.cursor + 1),
+            KeyCode::PageUp => self.set_cursor(self.cursor.saturating_sub(page)),
+            KeyCode::PageDown => self.set_cursor(self.cursor + page),
+            KeyCode::Home => self.set_cursor(0),
+            KeyCode::End => self.set_cursor(last),
+            KeyCode::Enter => return self.activate(),
+            KeyCode::Char(' ') if self.multi_select => self.toggle_mark(),
+            _ => return Err(()),
+        }
+        Ok(HandleReturn::None)
+    }
+}
+
+impl Object for ListObject {
+    fn display(&self, backend: &mut dyn Backend, theme: &Theme) -> io::Result<()> {
+        let style = self.style.unwrap_or(theme.style(Role::Text));
+        let cursor_style = if self.is_selected {
+            self.selected_style
+                .unwrap_or(theme.style(Role::ButtonSelected))
+        } else {
+            style.bold()
+        };
+        let width = self.size.0;
+        let len = self.len();
+
+        for row in 0..self.size.1 {
+            backend.move_to(self.position.0, self.position.1 + row)?;
+            let index = self.scroll + row;
+            if index >= len {
+                backend.print(&" ".repeat(width), style)?;
+                continue;
+            }
+            let item = self.source.row(index);
+
+            let line = if self.multi_select {
+                let mark = if self.marked.contains(&index) {
+                    'x'
+                } else {
+                    ' '
+                };
+                format!("[{}] {}", mark, item)
+            } else {
+                item
+            };
+            let line = fit(&line, width, Overflow::Ellipsis);
+            let padding = " ".repeat(width.saturating_sub(display_width(&line)));
+            let row_style = if index == self.cursor {
+                cursor_style
+            } else {
+                style
+            };
+            backend.print(&format!("{}{}", line, padding), row_style)?;
+        }
+        Ok(())
+    }
+
+    fn handle(&mut self, command: ObjectCommand) -> Result<HandleReturn, ()> {
+        match command {
+            ObjectCommand::SetSize((width, height)) => {
+                self.size = (width, height);
+                self.scroll_by(0);
+                Ok(HandleReturn::None)
+            }
+            ObjectCommand::GetSize() => Ok(HandleReturn::Size(self.size)),
+            ObjectCommand::SetPosition((x, y)) => {
+                self.position = (x, y);
+                Ok(HandleReturn::None)
+            }
+            ObjectCommand::GetPosition() => Ok(HandleReturn::Position(self.position)),
+            ObjectCommand::SetItems(items) => {
+                self.source = Box::new(items);
+                let len = self.len();
+                self.marked.retain(|&i| i < len);
+                self.set_cursor(self.cursor);
+                Ok(HandleReturn::None)
+            }
+            ObjectCommand::SetMultiSelect(multi_select) => {
+                self.multi_select = multi_select;
+                if !multi_select {
+                    self.marked.clear();
+                }
+                Ok(HandleReturn::None)
+            }
+            // Multi-select lists report the marked rows, others the cursor row
+            ObjectCommand::GetValue() if self.multi_select => Ok(HandleReturn::Value(
+                Value::Indexes(self.marked.iter().copied().collect()),
+            )),
+            ObjectCommand::GetValue() => Ok(HandleReturn::Value(Value::Index(self.cursor))),
+            ObjectCommand::SetValue(Value::Index(index)) => {
+                self.set_cursor(index);
+                Ok(HandleReturn::None)
+            }
+            ObjectCommand::SetValue(Value::Indexes(indexes)) if self.multi_select => {
+                let len = self.len();
+                self.marked = indexes.into_iter().filter(|&i| i < len).collect();
+                Ok(HandleReturn::None)
+            }
+            ObjectCommand::GetSelected() => Ok(HandleReturn::Selected(self.is_selected)),
+            ObjectCommand::SetSelected(selected) => {
+                self.is_selected = selected;
+                Ok(HandleReturn::None)
+            }
+            ObjectCommand::SetStyle(style) => {
+                self.style = Some(style);
+                Ok(HandleReturn::None)
+            }
+            ObjectCommand::SetSelectedStyle(style) => {
+                self.selected_style = Some(style);
+                Ok(HandleReturn::None)
+            }
+            ObjectCommand::KeyPress(key) => self.handle_key(key.code),
+            ObjectCommand::Mouse(mouse) => match mouse.kind {
+                MouseEventKind::ScrollUp => {
+                    self.scroll_by(-(WHEEL_STEP as isize));
+                    Ok(HandleReturn::None)
+                }
+                MouseEventKind::ScrollDown => {
+                    self.scroll_by(WHEEL_STEP as isize);
+                    Ok(HandleReturn::None)
+                }
+                MouseEventKind::Down(MouseButton::Left) => {
+                    let index = self.scroll + (mouse.row as usize).saturating_sub(self.position.1);
+                    if index >= self.len() {
+                        return Err(());
+                    }
+                    // The first click on an unfocused widget only focuses it
+                    if self.is_selected && index == self.cursor {
+                        return self.activate();
+                    }
+                    self.set_cursor(index);
+                    Ok(HandleReturn::None)
+                }
+                _ => Err(()),
+            },
+            _ => Err(()),
+        }
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+
+    fn is_selectable(&self) -> bool {
+        true
+    }
+}
+
+impl Selectable for ListObject {}
@@ -3,8 +3,32 @@ pub mod text;
 pub mod container;
 // use container::ContainerObject;
 pub mod button;
+pub mod checkbox;
+pub mod dialog;
+pub mod grid;
+pub mod list;
+pub mod progress;
+pub mod radio;
+pub mod scroll_view;
+pub mod stack;
+pub mod table;
+pub mod tabs;
+pub mod text_area;
+pub mod text_input;
 
 use super::TuiHandler;
+use crate::backend::Backend;
+use crate::border::{BorderStyle, Borders};
+use crate::layout::{Constraint, HorizontalAlignment, Sides, VerticalAlignment};
+use crate::objects::grid::GridPlacement;
+use crate::objects::stack::LayerPlacement;
+use crate::objects::table::{Column, SortOrder};
+use crate::objects::tabs::KeyBinding;
+use crate::style::{Color, Style};
+use crate::theme::Theme;
+use crate::wrap::{Overflow, WrapMode};
+use crossterm::event::{KeyEvent, MouseEvent};
+use std::io;
 
 #[derive(Debug, Clone)]
 pub struct Handle {
@@ -27,6 +51,17 @@ pub enum HandleReturn {
     Selected(bool),
     ObjectCount(usize),
     Flow(container::FlowDirection),
+    Value(Value),
+}
+
+/// What an input object holds, read with `GetValue` and written with `SetValue`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Text(String),
+    Bool(bool),
+    Index(usize),
+    Indexes(Vec<usize>),
+    Ratio(f64),
 }
 
 impl HandleReturn {
@@ -64,10 +99,17 @@ impl HandleReturn {
             _ => panic!("Called unwrap_flow on a non-flow return value"),
         }
     }
+
+    pub fn unwrap_value(self) -> Value {
+        match self {
+            HandleReturn::Value(value) => value,
+            _ => panic!("Called unwrap_value on a non-value return value"),
+        }
+    }
 }
 
 pub trait Object {
-    fn display(&self);
+    fn display(&self, backend: &mut dyn Backend, theme: &Theme) -> io::Result<()>;
 
     fn handle(&mut self, command: ObjectCommand) -> Result<HandleReturn, ()>;
 
@@ -78,6 +120,40 @@ pub trait Object {
     fn is_selectable(&self) -> bool {
         false
     }
+
+    /// Whether this object holds children that handles can point into.
+    fn is_container(&self) -> bool {
+        false
+    }
+
+    fn child(&self, _index: usize) -> Option<&dyn Object> {
+        None
+    }
+
+    fn child_mut(&mut self, _index: usize) -> Option<&mut dyn Object> {
+        None
+    }
+
+    /// Whether child `index` is currently shown. Hidden children are skipped by drawing,
+    /// selection movement and mouse hit-testing.
+    fn child_visible(&self, _index: usize) -> bool {
+        true
+    }
+
+    /// The size the object needs to show all of its content, if it knows one. A
+    /// `ScrollViewObject` lays its content out at this size.
+    fn natural_size(&self) -> Option<(usize, usize)> {
+        None
+    }
+
+    /// How far the object has scrolled its children, so a child at `(x, y)` shows on
+    /// screen at `(x - dx, y - dy)`. `None` for objects that draw children in place.
+    fn scroll_offset(&self) -> Option<(usize, usize)> {
+        None
+    }
+
+    /// Sizes and positions the children, then has them do the same for theirs.
+    fn update_sizes(&mut self) {}
 }
 
 pub trait Selectable {}
@@ -85,12 +161,26 @@ pub trait Selectable {}
 pub enum ObjectCommand {
     SetText(String),
     SetSpacing(Vec<usize>),
+    SetConstraints(Vec<Constraint>),
     SetPosition((usize, usize)),
     GetPosition(),
     SetFlow(container::FlowDirection),
     GetFlow(),
     SetBorder(bool),
+    SetBorderStyle(BorderStyle),
+    SetBorders(Borders),
+    SetTitle(String, HorizontalAlignment),
+    SetFooter(String, HorizontalAlignment),
+    SetPadding(Sides),
+    SetMargin(Sides),
+    SetGap(usize),
+    SetCollapsedBorders(bool),
     AddObject(Box<dyn Object>),
+    PlaceObject(Box<dyn Object>, GridPlacement),
+    SetRows(Vec<Constraint>),
+    SetColumns(Vec<Constraint>),
+    AddLayer(Box<dyn Object>, LayerPlacement),
+    SetLayerVisible(usize, bool),
     SetSize((usize, usize)),
     GetSize(),
     GetObjects(Box<dyn FnOnce(&Vec<Box<dyn Object>>)>),
@@ -99,4 +189,37 @@ pub enum ObjectCommand {
     GetSelected(),
     MoveSelection(SelectionDirection),
     GetObjectCount(),
+    SetStyle(Style),
+    SetSelectedStyle(Style),
+    SetWrap(WrapMode),
+    SetAlignment(HorizontalAlignment),
+    SetVerticalAlignment(VerticalAlignment),
+    SetOverflow(Overflow),
+    SetPlaceholder(String),
+    SetMask(Option<char>),
+    SetMaxLength(Option<usize>),
+    SetLineNumbers(bool),
+    SetItems(Vec<String>),
+    SetMultiSelect(bool),
+    SetTableColumns(Vec<Column>),
+    SetTableRows(Vec<Vec<String>>),
+    SortBy(usize, SortOrder),
+    SetIndeterminate(bool),
+    SetThresholds(Vec<(f64, Color)>),
+    AddTab(String, Box<dyn Object>),
+    SetTabKeys(KeyBinding, KeyBinding),
+    /// The size to lay scrolled content out at, or `None` for its natural size.
+    SetVirtualSize(Option<(usize, usize)>),
+    ScrollTo((usize, usize)),
+    /// Scrolls as little as needed to bring the area at `position` with `size` into view.
+    ScrollIntoView((usize, usize), (usize, usize)),
+    /// Moves animations on by one step.
+    Tick(),
+    GetValue(),
+    SetValue(Value),
+    /// A key pressed while the object, or something inside it, is selected. `Err(())`
+    /// passes the key on to the parent and finally the key triggers.
+    KeyPress(KeyEvent),
+    /// A mouse event over the object. Unhandled events go on to its parents.
+    Mouse(MouseEvent),
 }
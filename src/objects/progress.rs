@@ -0,0 +1,199 @@
+use crate::backend::Backend;
+use crate::objects::*;
+use crate::style::{Color, Style};
+use crate::theme::{Role, Theme};
+use crate::wrap::{display_width, grapheme_width};
+use std::io;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Partly filled cells, from one eighth up to seven.
+const EIGHTHS: [&str; 7] = ["▏", "▎", "▍", "▌", "▋", "▊", "▉"];
+
+/// A horizontal bar filled to `value`, with a label centered over it. Update it with
+/// `TuiHandler::set_value` to redraw just the bar. In indeterminate mode a block moves
+/// back and forth instead, one step per `Tick`, which `TuiHandler::run` sends every
+/// `TICK_RATE`.
+pub struct ProgressBarObject {
+    /// How full the bar is, from 0.0 to 1.0.
+    pub value: f64,
+    /// Shown instead of the percentage.
+    pub label: Option<String>,
+    pub indeterminate: bool,
+    /// Bar colors by value: the last threshold the value has reached wins.
+    pub thresholds: Vec<(f64, Color)>,
+    phase: usize,
+    pub size: (usize, usize),
+    pub position: (usize, usize),
+    pub style: Option<Style>,
+}
+
+impl ProgressBarObject {
+    pub fn new() -> Box<Self> {
+        Box::new(ProgressBarObject {
+            value: 0.0,
+            label: None,
+            indeterminate: false,
+            thresholds: Vec::new(),
+            phase: 0,
+            size: (0, 0),
+            position: (0, 0),
+            style: None,
+        })
+    }
+
+    fn bar_style(&self, theme: &Theme) -> Style {
+        let style = self.style.unwrap_or(theme.style(Role::Text));
+        let color = self
+            .thresholds
+            .iter()
+            .filter(|(threshold, _)| self.value >= *threshold)
+            .max_by(|a, b| a.0.total_cmp(&b.0))
+            .map(|&(_, color)| color);
+        match color {
+            Some(color) => style.fg(color),
+            None => style,
+        }
+    }
+
+    fn label(&self) -> String {
+        match &self.label {
+            Some(label) => label.clone(),
+            None if self.indeterminate => String::new(),
+            None => format!("{:.0}%", self.value * 100.0),
+        }
+    }
+
+    /// The bar's cells, each paired with whether it counts as filled.
+    fn cells(&self) -> Vec<(&'static str, bool)> {
+        let width = self.size.0;
+        if self.indeterminate {
+            // Bounce a block a fifth of the width wide between the ends
+            let block = (width / 5).max(1).min(width);
+            let travel = width - block;
+            let offset = match travel {
+                0 => 0,
+                _ => {
+                    let step = self.phase % (2 * travel);
+                    if step < travel {
+                        step
+                    } else {
+                        2 * travel - step
+                    }
+                }
+            };
+            return (0..width)
+                .map(|x| {
+                    let filled = x >= offset && x < offset + block;
+                    (if filled { "█" } else { " " }, filled)
+                })
+                .collect();
+        }
+
+        let eighths = (self.value * (width * 8) as f64).round() as usize;
+        (0..width)
+            .map(|x| match eighths.saturating_sub(x * 8) {
+                0 => (" ", false),
+                n if n >= 8 => ("█", true),
+                n => (EIGHTHS[n - 1], n >= 4),
+            })
+            .collect()
+    }
+}
+
+impl Object for ProgressBarObject {
+    fn display(&self, backend: &mut dyn Backend, theme: &Theme) -> io::Result<()> {
+        let (width, height) = self.size;
+        if width == 0 || height == 0 {
+            return Ok(());
+        }
+        let style = self.bar_style(theme);
+        let cells = self.cells();
+
+        // Label characters take over their cells, inverted where the bar is behind them
+        let label = self.label();
+        let mut label_cells: Vec<Option<&str>> = vec![None; width];
+        let mut x = width.saturating_sub(display_width(&label)) / 2;
+        for grapheme in label.graphemes(true) {
+            let w = grapheme_width(grapheme);
+            if x + w > width {
+                break;
+            }
+            label_cells[x] = Some(grapheme);
+            for covered in label_cells.iter_mut().skip(x + 1).take(w.saturating_sub(1)) {
+                *covered = Some("");
+            }
+            x += w;
+        }
+
+        let middle = (height - 1) / 2;
+        for row in 0..height {
+            backend.move_to(self.position.0, self.position.1 + row)?;
+            for (x, &(symbol, filled)) in cells.iter().enumerate() {
+                match label_cells[x] {
+                    Some("") if row == middle => {}
+                    Some(grapheme) if row == middle => {
+                        let style = if filled { style.reverse() } else { style };
+                        backend.print(grapheme, style)?;
+                    }
+                    _ => backend.print(symbol, style)?,
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn handle(&mut self, command: ObjectCommand) -> Result<HandleReturn, ()> {
+        match command {
+            ObjectCommand::SetSize((width, height)) => {
+                self.size = (width, height);
+                Ok(HandleReturn::None)
+            }
+            ObjectCommand::GetSize() => Ok(HandleReturn::Size(self.size)),
+            ObjectCommand::SetPosition((x, y)) => {
+                self.position = (x, y);
+                Ok(HandleReturn::None)
+            }
+            ObjectCommand::GetPosition() => Ok(HandleReturn::Position(self.position)),
+            ObjectCommand::GetValue() => Ok(HandleReturn::Value(Value::Ratio(self.value))),
+            ObjectCommand::SetValue(Value::Ratio(value)) => {
+                self.value = if value.is_nan() {
+                    0.0
+                } else {
+                    value.clamp(0.0, 1.0)
+                };
+                Ok(HandleReturn::None)
+            }
+            ObjectCommand::SetText(label) => {
+                self.label = Some(label);
+                Ok(HandleReturn::None)
+            }
+            ObjectCommand::SetIndeterminate(indeterminate) => {
+                self.indeterminate = indeterminate;
+                self.phase = 0;
+                Ok(HandleReturn::None)
+            }
+            ObjectCommand::SetThresholds(thresholds) => {
+                self.thresholds = thresholds;
+                Ok(HandleReturn::None)
+            }
+            // Only the indeterminate block moves, so a plain bar needs no redraw
+            ObjectCommand::Tick() if self.indeterminate => {
+                self.phase = self.phase.wrapping_add(1);
+                Ok(HandleReturn::None)
+            }
+            ObjectCommand::SetStyle(style) => {
+                self.style = Some(style);
+                Ok(HandleReturn::None)
+            }
+            _ => Err(()),
+        }
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
@@ -0,0 +1,152 @@
+use crate::backend::Backend;
+use crate::objects::*;
+use crate::style::Style;
+use crate::theme::{Role, Theme};
+use crate::wrap::{display_width, truncate};
+use crossterm::event::{KeyCode, MouseButton, MouseEventKind};
+use std::io;
+
+/// One option per row, exactly one of them active. While selected, Up and Down move
+/// between options and Enter or Space picks one; a click picks the row under it. The
+/// callback gets the index of the newly active option.
+pub struct RadioGroupObject {
+    pub options: Vec<String>,
+    pub active: usize,
+    /// The option Up and Down have moved to.
+    pub cursor: usize,
+    pub is_selected: bool,
+    pub callback: Box<dyn FnMut(usize)>,
+    pub size: (usize, usize),
+    pub position: (usize, usize),
+    pub style: Option<Style>,
+    pub selected_style: Option<Style>,
+}
+
+impl RadioGroupObject {
+    pub fn new(options: &[&str], callback: Box<dyn FnMut(usize)>) -> Box<Self> {
+        Box::new(RadioGroupObject {
+            options: options.iter().map(|option| option.to_string()).collect(),
+            active: 0,
+            cursor: 0,
+            is_selected: false,
+            callback,
+            size: (0, 0),
+            position: (0, 0),
+            style: None,
+            selected_style: None,
+        })
+    }
+
+    fn activate(&mut self, index: usize) -> Result<HandleReturn, ()> {
+        if index >= self.options.len() {
+            return Err(());
+        }
+        self.cursor = index;
+        if index != self.active {
+            self.active = index;
+            (self.callback)(index);
+        }
+        Ok(HandleReturn::None)
+    }
+}
+
+impl Object for RadioGroupObject {
+    fn display(&self, backend: &mut dyn Backend, theme: &Theme) -> io::Result<()> {
+        let style = self.style.unwrap_or(theme.style(Role::Button));
+        let selected_style = self
+            .selected_style
+            .unwrap_or(theme.style(Role::ButtonSelected));
+
+        for (i, option) in self.options.iter().enumerate().take(self.size.1) {
+            backend.move_to(self.position.0, self.position.1 + i)?;
+            let mark = if i == self.active { '•' } else { ' ' };
+            let text = format!("({}) {}", mark, option);
+            let style = if self.is_selected && i == self.cursor {
+                selected_style
+            } else {
+                style
+            };
+            backend.print(truncate(&text, self.size.0), style)?;
+        }
+        Ok(())
+    }
+
+    fn handle(&mut self, command: ObjectCommand) -> Result<HandleReturn, ()> {
+        match command {
+            ObjectCommand::SetSize((width, height)) => {
+                self.size = (width, height);
+                Ok(HandleReturn::None)
+            }
+            ObjectCommand::GetSize() => Ok(HandleReturn::Size(self.size)),
+            ObjectCommand::SetPosition((x, y)) => {
+                self.position = (x, y);
+                Ok(HandleReturn::None)
+            }
+            ObjectCommand::GetPosition() => Ok(HandleReturn::Position(self.position)),
+            ObjectCommand::GetValue() => Ok(HandleReturn::Value(Value::Index(self.active))),
+            ObjectCommand::SetValue(Value::Index(index)) if index < self.options.len() => {
+                self.active = index;
+                self.cursor = index;
+                Ok(HandleReturn::None)
+            }
+            ObjectCommand::GetSelected() => Ok(HandleReturn::Selected(self.is_selected)),
+            ObjectCommand::SetSelected(selected) => {
+                self.is_selected = selected;
+                self.cursor = self.active;
+                Ok(HandleReturn::None)
+            }
+            ObjectCommand::SetStyle(style) => {
+                self.style = Some(style);
+                Ok(HandleReturn::None)
+            }
+            ObjectCommand::SetSelectedStyle(style) => {
+                self.selected_style = Some(style);
+                Ok(HandleReturn::None)
+            }
+            // Up past the first option and Down past the last fall through, so the
+            // selection can move on from the group
+            ObjectCommand::KeyPress(key) => match key.code {
+                KeyCode::Up if self.cursor > 0 => {
+                    self.cursor -= 1;
+                    Ok(HandleReturn::None)
+                }
+                KeyCode::Down if self.cursor + 1 < self.options.len() => {
+                    self.cursor += 1;
+                    Ok(HandleReturn::None)
+                }
+                KeyCode::Enter | KeyCode::Char(' ') => self.activate(self.cursor),
+                _ => Err(()),
+            },
+            ObjectCommand::Mouse(mouse) => match mouse.kind {
+                MouseEventKind::Down(MouseButton::Left) => {
+                    self.activate((mouse.row as usize).saturating_sub(self.position.1))
+                }
+                _ => Err(()),
+            },
+            _ => Err(()),
+        }
+    }
+
+    fn natural_size(&self) -> Option<(usize, usize)> {
+        let width = self
+            .options
+            .iter()
+            .map(|option| display_width(option))
+            .max();
+        Some((width.unwrap_or(0) + 4, self.options.len()))
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+
+    fn is_selectable(&self) -> bool {
+        true
+    }
+}
+
+impl Selectable for RadioGroupObject {}
@@ -0,0 +1,404 @@
+use crate::backend::Backend;
+use crate::buffer::Buffer;
+use crate::objects::*;
+use crate::style::Style;
+use crate::theme::{Role, Theme};
+use crate::wrap::grapheme_width;
+use crossterm::event::{KeyModifiers, MouseEventKind};
+use std::io;
+
+const WHEEL_STEP: usize = 3;
+
+/// Shows a window onto one child that is laid out at its natural size, or at
+/// `virtual_size` when that is set, instead of being squeezed into the view. Scrollbars
+/// appear along the right and bottom edges when the content doesn't fit, the wheel
+/// scrolls it (with Shift held for sideways), and selection movement scrolls it to keep
+/// `TuiHandler::selected` in view.
+pub struct ScrollViewObject {
+    pub content: Vec<Box<dyn Object>>,
+    pub virtual_size: Option<(usize, usize)>,
+    /// How far the content is scrolled right and down, in cells.
+    pub scroll: (usize, usize),
+    pub(crate) size: (usize, usize),
+    pub position: (usize, usize),
+    pub(crate) index: Vec<usize>,
+    /// The area left for the content once the scrollbars are in.
+    viewport: (usize, usize),
+    /// The size the content was laid out at.
+    extent: (usize, usize),
+}
+
+impl ScrollViewObject {
+    pub fn new() -> Box<Self> {
+        Box::new(ScrollViewObject {
+            content: Vec::new(),
+            virtual_size: None,
+            scroll: (0, 0),
+            size: (0, 0),
+            position: (0, 0),
+            index: Vec::new(),
+            viewport: (0, 0),
+            extent: (0, 0),
+        })
+    }
+
+    fn add(&mut self, mut obj: Box<dyn Object>) -> Result<HandleReturn, ()> {
+        // There is only one window, so only one child
+        if !self.content.is_empty() {
+            return Err(());
+        }
+        let mut indexes = self.index.clone();
+        indexes.push(0);
+        let _ = obj.handle(ObjectCommand::SetIndexes(indexes.clone()));
+        self.content.push(obj);
+        Ok(HandleReturn::ObjectHandle(Handle { indexes }))
+    }
+
+    /// Whether screen cell `(x, y)` is in the window onto the content rather than on a
+    /// scrollbar.
+    pub(crate) fn shows(&self, x: usize, y: usize) -> bool {
+        x >= self.position.0
+            && x < self.position.0 + self.viewport.0
+            && y >= self.position.1
+            && y < self.position.1 + self.viewport.1
+    }
+
+    /// Works out which scrollbars are needed and the size the content is laid out at.
+    fn layout(&mut self) {
+        let wanted = self
+            .virtual_size
+            .or_else(|| self.content.first().and_then(|obj| obj.natural_size()))
+            .unwrap_or((0, 0));
+
+        // A bar in one direction takes a cell from the other, which can make that one
+        // needed too
+        let mut bars = (false, false);
+        for _ in 0..2 {
+            let viewport = self.viewport_with(bars);
+            bars = (wanted.1 > viewport.1, wanted.0 > viewport.0);
+        }
+        self.viewport = self.viewport_with(bars);
+        self.extent = (wanted.0.max(self.viewport.0), wanted.1.max(self.viewport.1));
+        self.scroll_to(self.scroll);
+    }
+
+    fn viewport_with(&self, (vertical, horizontal): (bool, bool)) -> (usize, usize) {
+        (
+            self.size.0.saturating_sub(vertical as usize),
+            self.size.1.saturating_sub(horizontal as usize),
+        )
+    }
+
+    /// Scrolls to `(x, y)`, kept in range. Returns whether the view moved.
+    fn scroll_to(&mut self, (x, y): (usize, usize)) -> bool {
+        let previous = self.scroll;
+        self.scroll = (
+            x.min(self.extent.0.saturating_sub(self.viewport.0)),
+            y.min(self.extent.1.saturating_sub(self.viewport.1)),
+        );
+        self.scroll != previous
+    }
+
+    fn scroll_by(&mut self, dx: isize, dy: isize) -> Result<HandleReturn, ()> {
+        let x = self.scroll.0.saturating_add_signed(dx);
+        let y = self.scroll.1.saturating_add_signed(dy);
+        // Let a scroll view further out have the wheel once this one hits the end
+        if self.scroll_to((x, y)) {
+            Ok(HandleReturn::None)
+        } else {
+            Err(())
+        }
+    }
+
+    /// Scrolls as little as possible to bring `(position, size)`, in content
+    /// coordinates, into view. Things bigger than the view are lined up on their start.
+    fn scroll_into_view(&mut self, position: (usize, usize), size: (usize, usize)) {
+        let reveal = |scroll: usize, start: usize, length: usize, view: usize| {
+            let mut scroll = scroll;
+            if start + length > scroll + view {
+                scroll = (start + length).saturating_sub(view);
+            }
+            scroll.min(start)
+        };
+        let x = position.0.saturating_sub(self.position.0);
+        let y = position.1.saturating_sub(self.position.1);
+        self.scroll_to((
+            reveal(self.scroll.0, x, size.0, self.viewport.0),
+            reveal(self.scroll.1, y, size.1, self.viewport.1),
+        ));
+    }
+
+    fn display_content(&self, backend: &mut dyn Backend, theme: &Theme) -> io::Result<()> {
+        let (x, y) = self.position;
+        let mut window = Window {
+            buffer: Buffer::new((self.scroll.0 + self.viewport.0, self.viewport.1)),
+            origin: (x, y + self.scroll.1),
+            hidden: false,
+        };
+        if let Some(obj) = self.content.first() {
+            obj.display(&mut window, theme)?;
+        }
+
+        for row in 0..self.viewport.1 {
+            backend.move_to(x, y + row)?;
+            for column in 0..self.viewport.0 {
+                let cell = window
+                    .buffer
+                    .get(self.scroll.0 + column, row)
+                    .cloned()
+                    .unwrap_or_default();
+                if cell.symbol.is_empty() {
+                    // The rest of a wide character, already printed unless it was
+                    // scrolled off the left edge
+                    if column == 0 {
+                        backend.print(" ", cell.style)?;
+                    }
+                } else if column + 1 == self.viewport.0 && grapheme_width(&cell.symbol) > 1 {
+                    // Half of it would spill onto the scrollbar
+                    backend.print(" ", cell.style)?;
+                } else {
+                    backend.print(&cell.symbol, cell.style)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn display_scrollbars(&self, backend: &mut dyn Backend, theme: &Theme) -> io::Result<()> {
+        let style = theme.style(Role::Border);
+        let (x, y) = self.position;
+        let (width, height) = self.viewport;
+        let vertical = self.extent.1 > height;
+        let horizontal = self.extent.0 > width;
+
+        if vertical {
+            let (start, length) = thumb(height, self.extent.1, self.scroll.1);
+            for row in 0..height {
+                backend.move_to(x + width, y + row)?;
+                let symbol = if row >= start && row < start + length {
+                    "█"
+                } else {
+                    "░"
+                };
+                backend.print(symbol, style)?;
+            }
+        }
+        if horizontal {
+            let (start, length) = thumb(width, self.extent.0, self.scroll.0);
+            backend.move_to(x, y + height)?;
+            let bar: String = (0..width)
+                .map(|column| {
+                    if column >= start && column < start + length {
+                        '█'
+                    } else {
+                        '░'
+                    }
+                })
+                .collect();
+            backend.print(&bar, style)?;
+            if vertical {
+                backend.print(" ", style)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// What the content draws into: only the rows in view, from the content's left edge to
+/// the right edge of the view, so a large virtual size doesn't cost a large buffer.
+struct Window {
+    buffer: Buffer,
+    /// The content cell at the buffer's top left corner.
+    origin: (usize, usize),
+    /// Set while the cursor is on a row outside the view, so prints there are dropped.
+    hidden: bool,
+}
+
+impl Backend for Window {
+    fn move_to(&mut self, x: usize, y: usize) -> io::Result<()> {
+        let (Some(x), Some(y)) = (x.checked_sub(self.origin.0), y.checked_sub(self.origin.1))
+        else {
+            self.hidden = true;
+            return Ok(());
+        };
+        self.hidden = y >= self.buffer.size.1;
+        self.buffer.move_to(x, y)
+    }
+
+    fn print(&mut self, text: &str, style: Style) -> io::Result<()> {
+        if self.hidden {
+            return Ok(());
+        }
+        self.buffer.print(text, style)
+    }
+
+    fn print_joined(&mut self, text: &str, style: Style) -> io::Result<()> {
+        if self.hidden {
+            return Ok(());
+        }
+        self.buffer.print_joined(text, style)
+    }
+
+    fn clear(&mut self) -> io::Result<()> {
+        self.buffer.clear()
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn size(&self) -> io::Result<(usize, usize)> {
+        self.buffer.size()
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+/// Start and length of a scrollbar thumb along a track as long as the view.
+fn thumb(view: usize, extent: usize, scroll: usize) -> (usize, usize) {
+    if view == 0 || extent <= view {
+        return (0, view);
+    }
+    let length = (view * view / extent).clamp(1, view);
+    let start = (scroll * (view - length) + (extent - view) / 2) / (extent - view);
+    (start, length)
+}
+
+impl Object for ScrollViewObject {
+    fn display(&self, backend: &mut dyn Backend, theme: &Theme) -> io::Result<()> {
+        if self.size.0 == 0 || self.size.1 == 0 {
+            return Ok(());
+        }
+        self.display_content(backend, theme)?;
+        self.display_scrollbars(backend, theme)
+    }
+
+    fn handle(&mut self, command: ObjectCommand) -> Result<HandleReturn, ()> {
+        match command {
+            ObjectCommand::SetSize((width, height)) => {
+                self.size = (width, height);
+                Ok(HandleReturn::None)
+            }
+            ObjectCommand::GetSize() => Ok(HandleReturn::Size(self.size)),
+            ObjectCommand::SetPosition((x, y)) => {
+                self.position = (x, y);
+                Ok(HandleReturn::None)
+            }
+            ObjectCommand::GetPosition() => Ok(HandleReturn::Position(self.position)),
+            ObjectCommand::AddObject(obj) => self.add(obj),
+            ObjectCommand::SetVirtualSize(size) => {
+                self.virtual_size = size;
+                Ok(HandleReturn::None)
+            }
+            ObjectCommand::ScrollTo(scroll) => {
+                self.scroll_to(scroll);
+                Ok(HandleReturn::None)
+            }
+            ObjectCommand::ScrollIntoView(position, size) => {
+                self.scroll_into_view(position, size);
+                Ok(HandleReturn::None)
+            }
+            ObjectCommand::GetObjects(callback) => {
+                callback(&self.content);
+                Ok(HandleReturn::None)
+            }
+            ObjectCommand::SetIndexes(indexes) => {
+                self.index = indexes;
+                Ok(HandleReturn::None)
+            }
+            ObjectCommand::GetObjectCount() => Ok(HandleReturn::ObjectCount(self.content.len())),
+            ObjectCommand::Mouse(mouse) => {
+                let sideways = mouse.modifiers.contains(KeyModifiers::SHIFT);
+                let step = WHEEL_STEP as isize;
+                match mouse.kind {
+                    MouseEventKind::ScrollUp if sideways => self.scroll_by(-step, 0),
+                    MouseEventKind::ScrollDown if sideways => self.scroll_by(step, 0),
+                    MouseEventKind::ScrollUp => self.scroll_by(0, -step),
+                    MouseEventKind::ScrollDown => self.scroll_by(0, step),
+                    MouseEventKind::ScrollLeft => self.scroll_by(-step, 0),
+                    MouseEventKind::ScrollRight => self.scroll_by(step, 0),
+                    _ => Err(()),
+                }
+            }
+            _ => Err(()),
+        }
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+
+    fn is_container(&self) -> bool {
+        true
+    }
+
+    fn child(&self, index: usize) -> Option<&dyn Object> {
+        self.content.get(index).map(|obj| &**obj)
+    }
+
+    fn child_mut(&mut self, index: usize) -> Option<&mut dyn Object> {
+        match self.content.get_mut(index) {
+            Some(obj) => Some(&mut **obj),
+            None => None,
+        }
+    }
+
+    fn scroll_offset(&self) -> Option<(usize, usize)> {
+        Some(self.scroll)
+    }
+
+    /// Lays the child out unscrolled, starting at the view's top left corner. Drawing
+    /// and hit-testing shift it by `scroll`.
+    fn update_sizes(&mut self) {
+        self.layout();
+        let (extent, position) = (self.extent, self.position);
+        if let Some(obj) = self.content.first_mut() {
+            obj.handle(ObjectCommand::SetSize(extent)).unwrap();
+            obj.handle(ObjectCommand::SetPosition(position)).unwrap();
+            obj.update_sizes();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ScrollViewObject;
+    use crate::backend::test::TestBackend;
+    use crate::objects::container::ContainerObject;
+    use crate::objects::{Object, ObjectCommand};
+    use crate::theme::Theme;
+
+    #[test]
+    fn shows_the_scrolled_corner_of_a_large_virtual_area() {
+        let mut view = ScrollViewObject::new();
+        view.handle(ObjectCommand::AddObject(ContainerObject::new()))
+            .unwrap();
+        view.handle(ObjectCommand::SetVirtualSize(Some((5000, 5000))))
+            .unwrap();
+        view.handle(ObjectCommand::SetPosition((100, 50))).unwrap();
+        view.handle(ObjectCommand::SetSize((6, 4))).unwrap();
+        view.update_sizes();
+        view.handle(ObjectCommand::ScrollTo((usize::MAX, usize::MAX)))
+            .unwrap();
+        assert_eq!(view.scroll, (4995, 4997));
+
+        let mut backend = TestBackend::new(106, 54);
+        view.display(&mut backend, &Theme::default()).unwrap();
+        let window: Vec<String> = backend.lines()[50..]
+            .iter()
+            .map(|line| line.chars().skip(100).collect())
+            .collect();
+        // The container's bottom right corner, with the scrollbar thumbs at the ends
+        assert_eq!(window, ["    │░", "    │░", "────╯█", "░░░░█ "]);
+    }
+}
@@ -0,0 +1,162 @@
+use crate::backend::Backend;
+use crate::objects::{Handle, HandleReturn, Object, ObjectCommand};
+use crate::style::Style;
+use crate::theme::Theme;
+use std::io;
+
+/// Where a layer sits inside the stack's rectangle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LayerPlacement {
+    /// The whole rectangle.
+    #[default]
+    Fill,
+    /// A `(width, height)` box in the middle.
+    Centered(usize, usize),
+    /// A `(x, y, width, height)` box, relative to the stack's position.
+    At(usize, usize, usize, usize),
+}
+
+/// Paints its children on top of each other, first child at the bottom. Each layer
+/// blanks out its rectangle before drawing, so nothing underneath shows through.
+pub struct StackObject {
+    pub content: Vec<Box<dyn Object>>,
+    pub placements: Vec<LayerPlacement>,
+    pub visible: Vec<bool>,
+    pub(crate) size: (usize, usize),
+    pub position: (usize, usize),
+    pub(crate) index: Vec<usize>,
+}
+
+impl StackObject {
+    pub fn new() -> Box<Self> {
+        Box::new(StackObject {
+            content: Vec::new(),
+            placements: Vec::new(),
+            visible: Vec::new(),
+            size: (0, 0),
+            position: (0, 0),
+            index: Vec::new(),
+        })
+    }
+
+    pub fn add_layer(&mut self, obj: Box<dyn Object>, placement: LayerPlacement) {
+        self.content.push(obj);
+        self.placements.push(placement);
+        self.visible.push(true);
+    }
+
+    fn add(&mut self, mut obj: Box<dyn Object>, placement: LayerPlacement) -> HandleReturn {
+        let mut indexes = self.index.clone();
+        indexes.push(self.content.len());
+        let _ = obj.handle(ObjectCommand::SetIndexes(indexes.clone()));
+        self.add_layer(obj, placement);
+        HandleReturn::ObjectHandle(Handle { indexes })
+    }
+
+    fn layer_rect(&self, placement: LayerPlacement) -> ((usize, usize), (usize, usize)) {
+        let (width, height) = self.size;
+        let (x, y, w, h) = match placement {
+            LayerPlacement::Fill => (0, 0, width, height),
+            LayerPlacement::Centered(w, h) => {
+                let (w, h) = (w.min(width), h.min(height));
+                ((width - w) / 2, (height - h) / 2, w, h)
+            }
+            LayerPlacement::At(x, y, w, h) => {
+                let (x, y) = (x.min(width), y.min(height));
+                (x, y, w.min(width - x), h.min(height - y))
+            }
+        };
+        ((self.position.0 + x, self.position.1 + y), (w, h))
+    }
+}
+
+impl Object for StackObject {
+    fn display(&self, backend: &mut dyn Backend, theme: &Theme) -> io::Result<()> {
+        for (i, obj) in self.content.iter().enumerate() {
+            if !self.visible[i] {
+                continue;
+            }
+
+            let ((x, y), (width, height)) = self.layer_rect(self.placements[i]);
+            let blank = " ".repeat(width);
+            for row in 0..height {
+                backend.move_to(x, y + row)?;
+                backend.print(&blank, Style::default())?;
+            }
+
+            obj.display(backend, theme)?;
+        }
+        Ok(())
+    }
+
+    fn handle(&mut self, command: ObjectCommand) -> Result<HandleReturn, ()> {
+        match command {
+            ObjectCommand::SetSize((width, height)) => {
+                self.size = (width, height);
+                Ok(HandleReturn::None)
+            }
+            ObjectCommand::GetSize() => Ok(HandleReturn::Size(self.size)),
+            ObjectCommand::SetPosition((x, y)) => {
+                self.position = (x, y);
+                Ok(HandleReturn::None)
+            }
+            ObjectCommand::GetPosition() => Ok(HandleReturn::Position(self.position)),
+            ObjectCommand::AddObject(obj) => Ok(self.add(obj, LayerPlacement::Fill)),
+            ObjectCommand::AddLayer(obj, placement) => Ok(self.add(obj, placement)),
+            ObjectCommand::SetLayerVisible(index, visible) => match self.visible.get_mut(index) {
+                Some(layer) => {
+                    *layer = visible;
+                    Ok(HandleReturn::None)
+                }
+                None => Err(()),
+            },
+            ObjectCommand::GetObjects(callback) => {
+                callback(&self.content);
+                Ok(HandleReturn::None)
+            }
+            ObjectCommand::SetIndexes(indexes) => {
+                self.index = indexes;
+                Ok(HandleReturn::None)
+            }
+            ObjectCommand::GetObjectCount() => Ok(HandleReturn::ObjectCount(self.content.len())),
+            _ => Err(()),
+        }
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+
+    fn is_container(&self) -> bool {
+        true
+    }
+
+    fn child(&self, index: usize) -> Option<&dyn Object> {
+        self.content.get(index).map(|obj| &**obj)
+    }
+
+    fn child_mut(&mut self, index: usize) -> Option<&mut dyn Object> {
+        match self.content.get_mut(index) {
+            Some(obj) => Some(&mut **obj),
+            None => None,
+        }
+    }
+
+    fn child_visible(&self, index: usize) -> bool {
+        self.visible.get(index).copied().unwrap_or(false)
+    }
+
+    fn update_sizes(&mut self) {
+        for i in 0..self.content.len() {
+            let (position, size) = self.layer_rect(self.placements[i]);
+            let obj = &mut self.content[i];
+            obj.handle(ObjectCommand::SetSize(size)).unwrap();
+            obj.handle(ObjectCommand::SetPosition(position)).unwrap();
+            obj.update_sizes();
+        }
+    }
+}
@@ -0,0 +1,424 @@
+use crate::backend::Backend;
+use crate::layout::{solve, Constraint, HorizontalAlignment};
+use crate::objects::*;
+use crate::style::Style;
+use crate::theme::{Role, Theme};
+use crate::wrap::{display_width, fit, Overflow};
+use crossterm::event::{KeyCode, MouseButton, MouseEventKind};
+use std::cmp::Ordering;
+use std::io;
+
+/// Cells between columns.
+const COLUMN_GAP: usize = 1;
+const WHEEL_STEP: usize = 3;
+
+#[derive(Debug, Clone)]
+pub struct Column {
+    pub title: String,
+    pub width: Constraint,
+    pub alignment: HorizontalAlignment,
+}
+
+impl Column {
+    pub fn new(title: &str, width: Constraint) -> Self {
+        Column {
+            title: title.to_string(),
+            width,
+            alignment: HorizontalAlignment::Left,
+        }
+    }
+
+    pub fn align(mut self, alignment: HorizontalAlignment) -> Self {
+        self.alignment = alignment;
+        self
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortOrder {
+    Ascending,
+    Descending,
+}
+
+/// Rows under a header that stays put while the body scrolls. While selected, the
+/// arrow keys, PageUp, PageDown, Home and End move the cursor row and Enter activates
+/// it. `s` sorts by the next column and `r` reverses the order; clicking a header
+/// sorts by that column, clicking it again reverses.
+pub struct TableObject {
+    pub columns: Vec<Column>,
+    pub rows: Vec<Vec<String>>,
+    /// Indexes into `rows` in the order they are shown.
+    order: Vec<usize>,
+    pub sort: Option<(usize, SortOrder)>,
+    /// Cursor position among the shown rows.
+    pub cursor: usize,
+    pub scroll: usize,
+    pub is_selected: bool,
+    /// Called with the index into `rows` of the activated row.
+    pub callback: Box<dyn FnMut(usize)>,
+    pub size: (usize, usize),
+    pub position: (usize, usize),
+    pub style: Option<Style>,
+    pub selected_style: Option<Style>,
+}
+
+impl TableObject {
+    pub fn new(columns: Vec<Column>, callback: Box<dyn FnMut(usize)>) -> Box<Self> {
+        Box::new(TableObject {
+            columns,
+            rows: Vec::new(),
+            order: Vec::new(),
+            sort: None,
+            cursor: 0,
+            scroll: 0,
+            is_selected: false,
+            callback,
+            size: (0, 0),
+            position: (0, 0),
+            style: None,
+            selected_style: None,
+        })
+    }
+
+    /// Rows of the body that fit under the header.
+    fn body_height(&self) -> usize {
+        self.size.1.saturating_sub(1)
+    }
+
+    /// Start and width of every column.
+    fn column_spans(&self) -> Vec<(usize, usize)> {
+        let constraints: Vec<Constraint> = self.columns.iter().map(|c| c.width).collect();
+        let gaps = COLUMN_GAP * constraints.len().saturating_sub(1);
+        let mut x = 0;
+        solve(self.size.0.saturating_sub(gaps), &constraints)
+            .into_iter()
+            .map(|width| {
+                let span = (x, width);
+                x += width + COLUMN_GAP;
+                span
+            })
+            .collect()
+    }
+
+    fn set_cursor(&mut self, cursor: usize) {
+        self.cursor = cursor.min(self.order.len().saturating_sub(1));
+        let height = self.body_height().max(1);
+        if self.cursor < self.scroll {
+            self.scroll = self.cursor;
+        } else if self.cursor >= self.scroll + height {
+            self.scroll = self.cursor + 1 - height;
+        }
+    }
+
+    fn scroll_by(&mut self, delta: isize) {
+        let max = self.order.len().saturating_sub(self.body_height());
+        self.scroll = self.scroll.saturating_add_signed(delta).min(max);
+    }
+
+    /// Re-sorts the shown order, keeping the cursor on the same row.
+    fn apply_sort(&mut self) {
+        let current = self.order.get(self.cursor).copied();
+        self.order = (0..self.rows.len()).collect();
+        if let Some((column, order)) = self.sort {
+            let rows = &self.rows;
+            let cell = |row: usize| rows[row].get(column).map_or("", String::as_str);
+            self.order.sort_by(|&a, &b| {
+                let ordering = compare_cells(cell(a), cell(b));
+                match order {
+                    SortOrder::Ascending => ordering,
+                    SortOrder::Descending => ordering.reverse(),
+                }
+            });
+        }
+        let cursor = current
+            .and_then(|row| self.order.iter().position(|&r| r == row))
+            .unwrap_or(0);
+        self.set_cursor(cursor);
+    }
+
+    /// Sorts by `column`, or flips the order if the table is already sorted by it.
+    fn sort_by(&mut self, column: usize) {
+        self.sort = match self.sort {
+            Some((current, SortOrder::Ascending)) if current == column => {
+                Some((column, SortOrder::Descending))
+            }
+            _ => Some((column, SortOrder::Ascending)),
+        };
+        self.apply_sort();
+    }
+
+    fn activate(&mut self) -> Result<HandleReturn, ()> {
+        if let Some(&row) = self.order.get(self.cursor) {
+            (self.callback)(row);
+        }
+        Ok(HandleReturn::None)
+    }
+
+    fn handle_key(&mut self, code: KeyCode) -> Result<HandleReturn, ()> {
+        let page = self.body_height().max(1);
+        let last = self.order.len().saturating_sub(1);
+        match code {
+            KeyCode::Up if self.cursor > 0 => self.set_cursor(self.cursor - 1),
+            KeyCode::Down if self.cursor < last => self.set_cursor(self.cursor + 1),
+            KeyCode::PageUp => self.set_cursor(self.cursor.saturating_sub(page)),
+            KeyCode::PageDown => self.set_cursor(self.cursor + page),
+            KeyCode::Home => self.set_cursor(0),
+            KeyCode::End => self.set_cursor(last),
+            KeyCode::Enter => return self.activate(),
+            KeyCode::Char('s') if !self.columns.is_empty() => {
+                let next = self.sort.map_or(0, |(column, _)| column + 1) % self.columns.len();
+                self.sort = Some((next, SortOrder::Ascending));
+                self.apply_sort();
+            }
+            KeyCode::Char('r') if self.sort.is_some() => {
+                if let Some((_, order)) = &mut self.sort {
+                    *order = match order {
+                        SortOrder::Ascending => SortOrder::Descending,
+                        SortOrder::Descending => SortOrder::Ascending,
+                    };
+                }
+                self.apply_sort();
+            }
+            _ => return Err(()),
+        }
+        Ok(HandleReturn::None)
+    }
+
+    fn display_row(
+        &self,
+        backend: &mut dyn Backend,
+        y: usize,
+        cells: &[String],
+        style: Style,
+    ) -> io::Result<()> {
+        let spans = self.column_spans();
+        backend.move_to(self.position.0, y)?;
+        let mut line = String::new();
+        let mut used = 0;
+        for (i, (column, (x, width))) in self.columns.iter().zip(spans).enumerate() {
+            line.push_str(&" ".repeat(x.saturating_sub(used)));
+            let text = fit(
+                cells.get(i).map_or("", String::as_str),
+                width,
+                Overflow::Ellipsis,
+            );
+            let text_width = display_width(&text);
+            let left = column.alignment.offset(width, text_width);
+            line.push_str(&" ".repeat(left));
+            line.push_str(&text);
+            line.push_str(&" ".repeat(width - left - text_width));
+            used = x + width;
+        }
+        line.push_str(&" ".repeat(self.size.0.saturating_sub(used)));
+        backend.print(&line, style)
+    }
+}
+
+/// Numbers compare as numbers and come before everything else, which compares as text.
+fn compare_cells(a: &str, b: &str) -> Ordering {
+    match (number(a), number(b)) {
+        (Some(a), Some(b)) => a.total_cmp(&b),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => a.cmp(b),
+    }
+}
+
+/// The cell as a number, if it is one. `NaN` is left as text so numbers stay ordered.
+fn number(cell: &str) -> Option<f64> {
+    cell.trim().parse::<f64>().ok().filter(|n| !n.is_nan())
+}
+
+impl Object for TableObject {
+    fn display(&self, backend: &mut dyn Backend, theme: &Theme) -> io::Result<()> {
+        if self.size.1 == 0 {
+            return Ok(());
+        }
+        let style = self.style.unwrap_or(theme.style(Role::Text));
+        let cursor_style = if self.is_selected {
+            self.selected_style
+                .unwrap_or(theme.style(Role::ButtonSelected))
+        } else {
+            style.bold()
+        };
+
+        // Shorten a sorted column's title rather than its arrow
+        let header: Vec<String> = self
+            .columns
+            .iter()
+            .zip(self.column_spans())
+            .enumerate()
+            .map(|(i, (column, (_, width)))| match self.sort {
+                Some((sorted, order)) if sorted == i => {
+                    let arrow = match order {
+                        SortOrder::Ascending => "▲",
+                        SortOrder::Descending => "▼",
+                    };
+                    let title = fit(&column.title, width.saturating_sub(2), Overflow::Ellipsis);
+                    format!("{} {}", title, arrow)
+                }
+                _ => column.title.clone(),
+            })
+            .collect();
+        self.display_row(backend, self.position.1, &header, theme.style(Role::Title))?;
+
+        let empty = Vec::new();
+        for row in 0..self.body_height() {
+            let index = self.scroll + row;
+            let (cells, row_style) = match self.order.get(index) {
+                Some(&data) if index == self.cursor => (&self.rows[data], cursor_style),
+                Some(&data) => (&self.rows[data], style),
+                None => (&empty, style),
+            };
+            self.display_row(backend, self.position.1 + 1 + row, cells, row_style)?;
+        }
+        Ok(())
+    }
+
+    fn handle(&mut self, command: ObjectCommand) -> Result<HandleReturn, ()> {
+        match command {
+            ObjectCommand::SetSize((width, height)) => {
+                self.size = (width, height);
+                self.scroll_by(0);
+                Ok(HandleReturn::None)
+            }
+            ObjectCommand::GetSize() => Ok(HandleReturn::Size(self.size)),
+            ObjectCommand::SetPosition((x, y)) => {
+                self.position = (x, y);
+                Ok(HandleReturn::None)
+            }
+            ObjectCommand::GetPosition() => Ok(HandleReturn::Position(self.position)),
+            ObjectCommand::SetTableColumns(columns) => {
+                self.columns = columns;
+                if self
+                    .sort
+                    .is_some_and(|(column, _)| column >= self.columns.len())
+                {
+                    self.sort = None;
+                }
+                self.apply_sort();
+                Ok(HandleReturn::None)
+            }
+            ObjectCommand::SetTableRows(rows) => {
+                self.rows = rows;
+                self.apply_sort();
+                Ok(HandleReturn::None)
+            }
+            ObjectCommand::SortBy(column, order) if column < self.columns.len() => {
+                self.sort = Some((column, order));
+                self.apply_sort();
+                Ok(HandleReturn::None)
+            }
+            // The value is the row's index in `rows`, however the table is sorted
+            ObjectCommand::GetValue() => match self.order.get(self.cursor) {
+                Some(&row) => Ok(HandleReturn::Value(Value::Index(row))),
+                None => Ok(HandleReturn::None),
+            },
+            ObjectCommand::SetValue(Value::Index(row)) => {
+                let cursor = self.order.iter().position(|&r| r == row).ok_or(())?;
+                self.set_cursor(cursor);
+                Ok(HandleReturn::None)
+            }
+            ObjectCommand::GetSelected() => Ok(HandleReturn::Selected(self.is_selected)),
+            ObjectCommand::SetSelected(selected) => {
+                self.is_selected = selected;
+                Ok(HandleReturn::None)
+            }
+            ObjectCommand::SetStyle(style) => {
+                self.style = Some(style);
+                Ok(HandleReturn::None)
+            }
+            ObjectCommand::SetSelectedStyle(style) => {
+                self.selected_style = Some(style);
+                Ok(HandleReturn::None)
+            }
+            ObjectCommand::KeyPress(key) => self.handle_key(key.code),
+            ObjectCommand::Mouse(mouse) => match mouse.kind {
+                MouseEventKind::ScrollUp => {
+                    self.scroll_by(-(WHEEL_STEP as isize));
+                    Ok(HandleReturn::None)
+                }
+                MouseEventKind::ScrollDown => {
+                    self.scroll_by(WHEEL_STEP as isize);
+                    Ok(HandleReturn::None)
+                }
+                MouseEventKind::Down(MouseButton::Left) => {
+                    let x = (mouse.column as usize).saturating_sub(self.position.0);
+                    let y = (mouse.row as usize).saturating_sub(self.position.1);
+                    if y == 0 {
+                        let column = self
+                            .column_spans()
+                            .iter()
+                            .position(|&(start, width)| x >= start && x < start + width)
+                            .ok_or(())?;
+                        self.sort_by(column);
+                        return Ok(HandleReturn::None);
+                    }
+                    let index = self.scroll + y - 1;
+                    if index >= self.order.len() {
+                        return Err(());
+                    }
+                    // The first click on an unfocused widget only focuses it
+                    if self.is_selected && index == self.cursor {
+                        return self.activate();
+                    }
+                    self.set_cursor(index);
+                    Ok(HandleReturn::None)
+                }
+                _ => Err(()),
+            },
+            _ => Err(()),
+        }
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+
+    fn is_selectable(&self) -> bool {
+        true
+    }
+}
+
+impl Selectable for TableObject {}
+
+#[cfg(test)]
+mod tests {
+    use super::compare_cells;
+    use std::cmp::Ordering;
+
+    const CELLS: [&str; 10] = [
+        "2", "10", "1a", "NaN", "-3.5", "abc", "", " 7 ", "inf", "2.0",
+    ];
+
+    #[test]
+    fn cell_comparison_is_a_total_order() {
+        for a in CELLS {
+            assert_eq!(compare_cells(a, a), Ordering::Equal);
+            for b in CELLS {
+                assert_eq!(compare_cells(a, b), compare_cells(b, a).reverse());
+                for c in CELLS {
+                    if compare_cells(a, b) != Ordering::Greater
+                        && compare_cells(b, c) != Ordering::Greater
+                    {
+                        assert_ne!(compare_cells(a, c), Ordering::Greater, "{a} {b} {c}");
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn numbers_sort_before_text() {
+        let mut cells = CELLS.to_vec();
+        cells.sort_by(|a, b| compare_cells(a, b));
+        assert_eq!(
+            cells,
+            ["-3.5", "2", "2.0", " 7 ", "10", "inf", "", "1a", "NaN", "abc"]
+        );
+    }
+}
@@ -0,0 +1,210 @@
+use crate::backend::Backend;
+use crate::objects::*;
+use crate::theme::{Role, Theme};
+use crate::wrap::{display_width, truncate};
+use crossterm::event::{KeyCode, KeyModifiers, MouseButton, MouseEventKind};
+use std::io;
+
+/// A key and the modifiers that have to be held with it.
+pub type KeyBinding = (KeyCode, KeyModifiers);
+
+/// A header strip over one child per tab. Only the active tab is laid out, drawn and
+/// reachable by selection. The tab keys (Ctrl+PageDown and Ctrl+PageUp unless changed)
+/// work from anywhere inside, and clicking a title switches to it.
+pub struct TabsObject {
+    pub content: Vec<Box<dyn Object>>,
+    pub titles: Vec<String>,
+    pub active: usize,
+    pub next_key: KeyBinding,
+    pub previous_key: KeyBinding,
+    pub(crate) size: (usize, usize),
+    pub position: (usize, usize),
+    pub(crate) index: Vec<usize>,
+}
+
+impl TabsObject {
+    pub fn new() -> Box<Self> {
+        Box::new(TabsObject {
+            content: Vec::new(),
+            titles: Vec::new(),
+            active: 0,
+            next_key: (KeyCode::PageDown, KeyModifiers::CONTROL),
+            previous_key: (KeyCode::PageUp, KeyModifiers::CONTROL),
+            size: (0, 0),
+            position: (0, 0),
+            index: Vec::new(),
+        })
+    }
+
+    pub fn add_tab(&mut self, title: &str, obj: Box<dyn Object>) {
+        self.content.push(obj);
+        self.titles.push(title.to_string());
+    }
+
+    fn add(&mut self, title: String, mut obj: Box<dyn Object>) -> HandleReturn {
+        let mut indexes = self.index.clone();
+        indexes.push(self.content.len());
+        let _ = obj.handle(ObjectCommand::SetIndexes(indexes.clone()));
+        self.add_tab(&title, obj);
+        HandleReturn::ObjectHandle(Handle { indexes })
+    }
+
+    /// Start and width of each title in the header.
+    fn title_spans(&self) -> Vec<(usize, usize)> {
+        let mut x = 0;
+        self.titles
+            .iter()
+            .map(|title| {
+                let span = (x, display_width(title) + 2);
+                x += span.1 + 1;
+                span
+            })
+            .collect()
+    }
+
+    fn switch(&mut self, active: usize) -> Result<HandleReturn, ()> {
+        if active >= self.content.len() {
+            return Err(());
+        }
+        self.active = active;
+        Ok(HandleReturn::None)
+    }
+}
+
+impl Object for TabsObject {
+    fn display(&self, backend: &mut dyn Backend, theme: &Theme) -> io::Result<()> {
+        if self.size.1 == 0 {
+            return Ok(());
+        }
+
+        backend.move_to(self.position.0, self.position.1)?;
+        let mut used = 0;
+        for (i, (title, (x, _))) in self.titles.iter().zip(self.title_spans()).enumerate() {
+            if x >= self.size.0 {
+                break;
+            }
+            if x > used {
+                backend.print(&" ".repeat(x - used), theme.style(Role::Text))?;
+            }
+            let style = if i == self.active {
+                theme.style(Role::ButtonSelected)
+            } else {
+                theme.style(Role::Button)
+            };
+            let label = format!(" {} ", title);
+            let shown = truncate(&label, self.size.0 - x);
+            backend.print(shown, style)?;
+            used = x + display_width(shown);
+        }
+        backend.print(
+            &" ".repeat(self.size.0.saturating_sub(used)),
+            theme.style(Role::Text),
+        )?;
+
+        match self.content.get(self.active) {
+            Some(obj) => obj.display(backend, theme),
+            None => Ok(()),
+        }
+    }
+
+    fn handle(&mut self, command: ObjectCommand) -> Result<HandleReturn, ()> {
+        match command {
+            ObjectCommand::SetSize((width, height)) => {
+                self.size = (width, height);
+                Ok(HandleReturn::None)
+            }
+            ObjectCommand::GetSize() => Ok(HandleReturn::Size(self.size)),
+            ObjectCommand::SetPosition((x, y)) => {
+                self.position = (x, y);
+                Ok(HandleReturn::None)
+            }
+            ObjectCommand::GetPosition() => Ok(HandleReturn::Position(self.position)),
+            ObjectCommand::AddObject(obj) => {
+                let title = format!("Tab {}", self.content.len() + 1);
+                Ok(self.add(title, obj))
+            }
+            ObjectCommand::AddTab(title, obj) => Ok(self.add(title, obj)),
+            ObjectCommand::SetTabKeys(next, previous) => {
+                self.next_key = next;
+                self.previous_key = previous;
+                Ok(HandleReturn::None)
+            }
+            ObjectCommand::GetValue() => Ok(HandleReturn::Value(Value::Index(self.active))),
+            ObjectCommand::SetValue(Value::Index(active)) => self.switch(active),
+            ObjectCommand::GetObjects(callback) => {
+                callback(&self.content);
+                Ok(HandleReturn::None)
+            }
+            ObjectCommand::SetIndexes(indexes) => {
+                self.index = indexes;
+                Ok(HandleReturn::None)
+            }
+            ObjectCommand::GetObjectCount() => Ok(HandleReturn::ObjectCount(self.content.len())),
+            ObjectCommand::KeyPress(key) => {
+                let count = self.content.len();
+                if count == 0 {
+                    return Err(());
+                }
+                if (key.code, key.modifiers) == self.next_key {
+                    self.switch((self.active + 1) % count)
+                } else if (key.code, key.modifiers) == self.previous_key {
+                    self.switch((self.active + count - 1) % count)
+                } else {
+                    Err(())
+                }
+            }
+            ObjectCommand::Mouse(mouse) => match mouse.kind {
+                MouseEventKind::Down(MouseButton::Left)
+                    if mouse.row as usize == self.position.1 =>
+                {
+                    let x = (mouse.column as usize).saturating_sub(self.position.0);
+                    let tab = self
+                        .title_spans()
+                        .iter()
+                        .position(|&(start, width)| x >= start && x < start + width)
+                        .ok_or(())?;
+                    self.switch(tab)
+                }
+                _ => Err(()),
+            },
+            _ => Err(()),
+        }
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+
+    fn is_container(&self) -> bool {
+        true
+    }
+
+    fn child(&self, index: usize) -> Option<&dyn Object> {
+        self.content.get(index).map(|obj| &**obj)
+    }
+
+    fn child_mut(&mut self, index: usize) -> Option<&mut dyn Object> {
+        match self.content.get_mut(index) {
+            Some(obj) => Some(&mut **obj),
+            None => None,
+        }
+    }
+
+    fn child_visible(&self, index: usize) -> bool {
+        index == self.active
+    }
+
+    fn update_sizes(&mut self) {
+        let position = (self.position.0, self.position.1 + 1);
+        let size = (self.size.0, self.size.1.saturating_sub(1));
+        if let Some(obj) = self.content.get_mut(self.active) {
+            obj.handle(ObjectCommand::SetSize(size)).unwrap();
+            obj.handle(ObjectCommand::SetPosition(position)).unwrap();
+            obj.update_sizes();
+        }
+    }
+}
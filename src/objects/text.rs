@@ -1,12 +1,21 @@
+use crate::backend::Backend;
+use crate::layout::{HorizontalAlignment, VerticalAlignment};
 use crate::objects::{HandleReturn, Object, ObjectCommand};
-use crossterm::{cursor, execute};
-use std::io::{self};
+use crate::style::Style;
+use crate::theme::{Role, Theme};
+use crate::wrap::{display_width, fit, wrap, Overflow, WrapMode};
+use std::io;
 
 #[derive(Debug, Clone)]
 pub struct TextObject {
     pub content: String,
     pub size: (usize, usize),
     pub position: (usize, usize),
+    pub style: Option<Style>,
+    pub wrap: WrapMode,
+    pub alignment: HorizontalAlignment,
+    pub vertical_alignment: VerticalAlignment,
+    pub overflow: Overflow,
 }
 
 impl TextObject {
@@ -15,32 +24,56 @@ impl TextObject {
             content: content.to_string(),
             size: (0, 0),
             position: (0, 0),
+            style: None,
+            wrap: WrapMode::default(),
+            alignment: HorizontalAlignment::default(),
+            vertical_alignment: VerticalAlignment::default(),
+            overflow: Overflow::default(),
         })
     }
+
+    /// The lines that end up on screen, already cut to fit `size`.
+    fn visible_lines(&self) -> Vec<String> {
+        let (width, height) = self.size;
+        let mut lines: Vec<String> = wrap(&self.content, width, self.wrap)
+            .iter()
+            .map(|line| fit(line, width, self.overflow))
+            .collect();
+
+        if lines.len() > height {
+            lines.truncate(height);
+            if self.overflow == Overflow::Ellipsis {
+                if let Some(last) = lines.last_mut() {
+                    *last = fit(&format!("{}…", last), width, Overflow::Ellipsis);
+                }
+            }
+        }
+        lines
+    }
 }
 
 impl Object for TextObject {
-    fn display(&self) {
-        let text_bits: Vec<&str> = self
-            .content
-            .as_bytes()
-            .chunks(self.size.0)
-            .map(|chunk| std::str::from_utf8(chunk).unwrap())
-            .collect();
+    fn display(&self, backend: &mut dyn Backend, theme: &Theme) -> io::Result<()> {
+        let text_bits = self.visible_lines();
+        let style = self.style.unwrap_or(theme.style(Role::Text));
+        let top = self.vertical_alignment.offset(self.size.1, text_bits.len());
 
         for i in 0..self.size.1 {
-            execute!(
-                io::stdout(),
-                cursor::MoveTo(self.position.0 as u16, self.position.1 as u16 + i as u16),
-            )
-            .unwrap();
+            backend.move_to(self.position.0, self.position.1 + i)?;
 
-            if i < text_bits.len() {
-                print!("{}", text_bits[i]);
-            } else {
-                print!(" ");
-            }
+            let line = match i.checked_sub(top).and_then(|i| text_bits.get(i)) {
+                Some(line) => line.as_str(),
+                None => "",
+            };
+            let width = display_width(line);
+            let left = self.alignment.offset(self.size.0, width);
+            let right = self.size.0.saturating_sub(left + width);
+            backend.print(
+                &format!("{}{}{}", " ".repeat(left), line, " ".repeat(right)),
+                style,
+            )?;
         }
+        Ok(())
     }
 
     fn handle(&mut self, command: ObjectCommand) -> Result<HandleReturn, ()> {
@@ -59,10 +92,35 @@ impl Object for TextObject {
                 self.content = new_text;
                 Ok(HandleReturn::None)
             }
+            ObjectCommand::SetStyle(style) => {
+                self.style = Some(style);
+                Ok(HandleReturn::None)
+            }
+            ObjectCommand::SetWrap(wrap) => {
+                self.wrap = wrap;
+                Ok(HandleReturn::None)
+            }
+            ObjectCommand::SetAlignment(alignment) => {
+                self.alignment = alignment;
+                Ok(HandleReturn::None)
+            }
+            ObjectCommand::SetVerticalAlignment(alignment) => {
+                self.vertical_alignment = alignment;
+                Ok(HandleReturn::None)
+            }
+            ObjectCommand::SetOverflow(overflow) => {
+                self.overflow = overflow;
+                Ok(HandleReturn::None)
+            }
             _ => Err(()),
         }
     }
 
+    fn natural_size(&self) -> Option<(usize, usize)> {
+        let width = self.content.lines().map(display_width).max().unwrap_or(0);
+        Some((width, self.content.lines().count().max(1)))
+    }
+
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
@@ -0,0 +1,569 @@
+use crate::backend::Backend;
+use crate::objects::text_input::{word_left, word_right};
+use crate::objects::{HandleReturn, Object, ObjectCommand, Selectable, Value};
+use crate::style::Style;
+use crate::theme::{Role, Theme};
+use crate::wrap::{grapheme_width, truncate, WrapMode};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::io;
+use unicode_segmentation::UnicodeSegmentation;
+
+const UNDO_LIMIT: usize = 1000;
+
+/// A `(line, column)` spot in the text, the column counted in grapheme clusters.
+pub type Position = (usize, usize);
+
+#[derive(Clone)]
+struct Snapshot {
+    lines: Vec<String>,
+    cursor: Position,
+}
+
+/// A multi-line editor. Keys reach it while it is `TuiHandler::selected`; Shift with
+/// the movement keys selects, Ctrl+Z and Ctrl+Y undo and redo.
+pub struct TextAreaObject {
+    pub lines: Vec<String>,
+    pub cursor: Position,
+    /// The other end of the selection, if there is one.
+    pub anchor: Option<Position>,
+    /// First visual row and first column shown.
+    scroll: (usize, usize),
+    pub wrap: WrapMode,
+    pub line_numbers: bool,
+    undo: Vec<Snapshot>,
+    redo: Vec<Snapshot>,
+    /// Whether the last edit was typing, so a run of characters undoes in one step.
+    typing: bool,
+    pub is_selected: bool,
+    pub size: (usize, usize),
+    pub position: (usize, usize),
+    pub style: Option<Style>,
+}
+
+impl TextAreaObject {
+    pub fn new(text: &str) -> Box<Self> {
+        let mut area = Box::new(TextAreaObject {
+            lines: Vec::new(),
+            cursor: (0, 0),
+            anchor: None,
+            scroll: (0, 0),
+            wrap: WrapMode::None,
+            line_numbers: false,
+            undo: Vec::new(),
+            redo: Vec::new(),
+            typing: false,
+            is_selected: false,
+            size: (0, 0),
+            position: (0, 0),
+            style: None,
+        });
+        area.set_text(text);
+        area
+    }
+
+    pub fn text(&self) -> String {
+        self.lines.join("\n")
+    }
+
+    /// The selected text, if anything is selected.
+    pub fn selected_text(&self) -> Option<String> {
+        let (start, end) = self.selection()?;
+        let mut lines = self.lines[start.0..=end.0].to_vec();
+        let last = lines.len() - 1;
+        let end_byte = byte_index(&lines[last], end.1);
+        lines[last].truncate(end_byte);
+        let start_byte = byte_index(&lines[0], start.1);
+        lines[0].replace_range(..start_byte, "");
+        Some(lines.join("\n"))
+    }
+
+    fn set_text(&mut self, text: &str) {
+        self.lines = text.split('\n').map(String::from).collect();
+        self.cursor = (0, 0);
+        self.anchor = None;
+        self.scroll = (0, 0);
+        self.undo.clear();
+        self.redo.clear();
+        self.typing = false;
+    }
+
+    fn line_len(&self, row: usize) -> usize {
+        self.lines[row].graphemes(true).count()
+    }
+
+    /// Ordered start and end of the selection, if it isn't empty.
+    fn selection(&self) -> Option<(Position, Position)> {
+        let anchor = self.anchor?;
+        if anchor == self.cursor {
+            return None;
+        }
+        Some((anchor.min(self.cursor), anchor.max(self.cursor)))
+    }
+
+    fn gutter_width(&self) -> usize {
+        if self.line_numbers {
+            self.lines.len().to_string().len() + 1
+        } else {
+            0
+        }
+    }
+
+    fn text_width(&self) -> usize {
+        self.size.0.saturating_sub(self.gutter_width())
+    }
+
+    /// Every row on screen as `(line, first column, end column)`. Without wrapping each
+    /// line is a single row.
+    fn visual_rows(&self) -> Vec<(usize, usize, usize)> {
+        let width = self.text_width();
+        let mut rows = Vec::new();
+        for (i, line) in self.lines.iter().enumerate() {
+            let graphemes: Vec<&str> = line.graphemes(true).collect();
+            if self.wrap == WrapMode::None || width == 0 {
+                rows.push((i, 0, graphemes.len()));
+                continue;
+            }
+            for (start, end) in segments(&graphemes, width, self.wrap) {
+                rows.push((i, start, end));
+            }
+        }
+        rows
+    }
+
+    /// The visual row the cursor is on. A cursor at a wrap point belongs to the row it
+    /// starts rather than the one it ends.
+    fn cursor_row(&self, rows: &[(usize, usize, usize)]) -> usize {
+        let (line, column) = self.cursor;
+        rows.iter()
+            .rposition(|&(row_line, start, _)| row_line == line && start <= column)
+            .unwrap_or(0)
+    }
+
+    fn scroll_to_cursor(&mut self) {
+        let rows = self.visual_rows();
+        let row = self.cursor_row(&rows);
+        let height = self.size.1.max(1);
+        if row < self.scroll.0 {
+            self.scroll.0 = row;
+        } else if row >= self.scroll.0 + height {
+            self.scroll.0 = row + 1 - height;
+        }
+
+        if self.wrap != WrapMode::None {
+            self.scroll.1 = 0;
+            return;
+        }
+        let (line, column) = self.cursor;
+        let x: usize = self.lines[line]
+            .graphemes(true)
+            .take(column)
+            .map(grapheme_width)
+            .sum();
+        let width = self.text_width().max(1);
+        if x < self.scroll.1 {
+            self.scroll.1 = x;
+        } else if x + 1 > self.scroll.1 + width {
+            self.scroll.1 = x + 1 - width;
+        }
+    }
+
+    /// Moves the cursor `delta` visual rows, keeping to the same screen column.
+    fn move_vertically(&mut self, delta: isize) {
+        let rows = self.visual_rows();
+        let row = self.cursor_row(&rows);
+        let (line, start, _) = rows[row];
+        let graphemes: Vec<&str> = self.lines[line].graphemes(true).collect();
+        let x: usize = graphemes[start..self.cursor.1]
+            .iter()
+            .map(|g| grapheme_width(g))
+            .sum();
+
+        let target = (row as isize + delta).clamp(0, rows.len() as isize - 1) as usize;
+        if target == row {
+            self.cursor.1 = if delta < 0 { 0 } else { graphemes.len() };
+            return;
+        }
+        let (line, start, end) = rows[target];
+        let graphemes: Vec<&str> = self.lines[line].graphemes(true).collect();
+        let mut column = start;
+        let mut used = 0;
+        while column < end && used + grapheme_width(graphemes[column]) <= x {
+            used += grapheme_width(graphemes[column]);
+            column += 1;
+        }
+        // Stay on this row rather than landing on the start of the next one
+        if column == end && end < graphemes.len() && column > start {
+            column -= 1;
+        }
+        self.cursor = (line, column);
+    }
+
+    fn move_cursor(&mut self, code: KeyCode, word: bool) {
+        let (line, column) = self.cursor;
+        let len = self.line_len(line);
+        let page = self.size.1.max(1) as isize;
+        match code {
+            KeyCode::Left if column == 0 && line > 0 => {
+                self.cursor = (line - 1, self.line_len(line - 1));
+            }
+            KeyCode::Left if word => {
+                let graphemes: Vec<&str> = self.lines[line].graphemes(true).collect();
+                self.cursor.1 = word_left(&graphemes, column);
+            }
+            KeyCode::Left => self.cursor.1 = column.saturating_sub(1),
+            KeyCode::Right if column == len && line + 1 < self.lines.len() => {
+                self.cursor = (line + 1, 0);
+            }
+            KeyCode::Right if word => {
+                let graphemes: Vec<&str> = self.lines[line].graphemes(true).collect();
+                self.cursor.1 = word_right(&graphemes, column);
+            }
+            KeyCode::Right => self.cursor.1 = (column + 1).min(len),
+            KeyCode::Up => self.move_vertically(-1),
+            KeyCode::Down => self.move_vertically(1),
+            KeyCode::PageUp => self.move_vertically(-page),
+            KeyCode::PageDown => self.move_vertically(page),
+            KeyCode::Home if word => self.cursor = (0, 0),
+            KeyCode::End if word => {
+                let last = self.lines.len() - 1;
+                self.cursor = (last, self.line_len(last));
+            }
+            KeyCode::Home => self.cursor.1 = 0,
+            KeyCode::End => self.cursor.1 = len,
+            _ => {}
+        }
+    }
+
+    /// Saves the text for undo. Consecutive typing shares one snapshot.
+    fn checkpoint(&mut self, typing: bool) {
+        if !(typing && self.typing) {
+            self.undo.push(Snapshot {
+                lines: self.lines.clone(),
+                cursor: self.cursor,
+            });
+            if self.undo.len() > UNDO_LIMIT {
+                self.undo.remove(0);
+            }
+        }
+        self.redo.clear();
+        self.typing = typing;
+    }
+
+    fn restore(&mut self, from_undo: bool) {
+        let (from, to) = if from_undo {
+            (&mut self.undo, &mut self.redo)
+        } else {
+            (&mut self.redo, &mut self.undo)
+        };
+        if let Some(snapshot) = from.pop() {
+            to.push(Snapshot {
+                lines: std::mem::replace(&mut self.lines, snapshot.lines),
+                cursor: self.cursor,
+            });
+            self.cursor = snapshot.cursor;
+            self.anchor = None;
+        }
+        self.typing = false;
+    }
+
+    fn delete_range(&mut self, start: Position, end: Position) {
+        let tail_byte = byte_index(&self.lines[end.0], end.1);
+        let tail = self.lines[end.0][tail_byte..].to_string();
+        self.lines.drain(start.0 + 1..=end.0);
+        let start_byte = byte_index(&self.lines[start.0], start.1);
+        self.lines[start.0].truncate(start_byte);
+        self.lines[start.0].push_str(&tail);
+        self.cursor = start;
+    }
+
+    fn delete_selection(&mut self) -> bool {
+        match self.selection() {
+            Some((start, end)) => {
+                self.delete_range(start, end);
+                self.anchor = None;
+                true
+            }
+            None => {
+                self.anchor = None;
+                false
+            }
+        }
+    }
+
+    fn insert(&mut self, text: &str) {
+        self.delete_selection();
+        let (line, column) = self.cursor;
+        let at = byte_index(&self.lines[line], column);
+        let tail = self.lines[line].split_off(at);
+
+        let mut pieces = text.split('\n');
+        self.lines[line].push_str(pieces.next().unwrap_or(""));
+        let mut row = line;
+        for piece in pieces {
+            row += 1;
+            self.lines.insert(row, piece.to_string());
+        }
+        // Combining marks can merge into the cluster before them, so count afterwards
+        let column = self.line_len(row);
+        self.lines[row].push_str(&tail);
+        self.cursor = (row, column);
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> Result<HandleReturn, ()> {
+        let control = key.modifiers.contains(KeyModifiers::CONTROL);
+        let shift = key.modifiers.contains(KeyModifiers::SHIFT);
+        let (line, column) = self.cursor;
+        match key.code {
+            KeyCode::Char('z') if control && shift => self.restore(false),
+            KeyCode::Char('z') if control => self.restore(true),
+            KeyCode::Char('y' | 'Z') if control => self.restore(false),
+            KeyCode::Char('a') if control => {
+                let last = self.lines.len() - 1;
+                self.anchor = Some((0, 0));
+                self.cursor = (last, self.line_len(last));
+            }
+            KeyCode::Char(_) if control || key.modifiers.contains(KeyModifiers::ALT) => {
+                return Err(());
+            }
+            KeyCode::Char(c) => {
+                // Typing over a selection starts a new undo step, the rest of the run joins it
+                self.checkpoint(self.selection().is_none());
+                self.typing = true;
+                self.insert(c.encode_utf8(&mut [0; 4]));
+            }
+            KeyCode::Enter => {
+                self.checkpoint(false);
+                self.insert("\n");
+            }
+            KeyCode::Backspace | KeyCode::Delete if self.selection().is_some() => {
+                self.checkpoint(false);
+                self.delete_selection();
+            }
+            KeyCode::Backspace if column > 0 => {
+                self.checkpoint(false);
+                self.delete_range((line, column - 1), (line, column));
+            }
+            KeyCode::Backspace if line > 0 => {
+                self.checkpoint(false);
+                let end = self.line_len(line - 1);
+                self.delete_range((line - 1, end), (line, 0));
+            }
+            KeyCode::Delete if column < self.line_len(line) => {
+                self.checkpoint(false);
+                self.delete_range((line, column), (line, column + 1));
+            }
+            KeyCode::Delete if line + 1 < self.lines.len() => {
+                self.checkpoint(false);
+                self.delete_range((line, column), (line + 1, 0));
+            }
+            KeyCode::Backspace | KeyCode::Delete => {}
+            code @ (KeyCode::Left
+            | KeyCode::Right
+            | KeyCode::Up
+            | KeyCode::Down
+            | KeyCode::Home
+            | KeyCode::End
+            | KeyCode::PageUp
+            | KeyCode::PageDown) => {
+                if shift {
+                    self.anchor.get_or_insert(self.cursor);
+                } else {
+                    self.anchor = None;
+                }
+                self.typing = false;
+                self.move_cursor(code, control);
+            }
+            _ => return Err(()),
+        }
+        self.scroll_to_cursor();
+        Ok(HandleReturn::None)
+    }
+}
+
+/// Byte offset of grapheme `index` in `line`, or its end.
+fn byte_index(line: &str, index: usize) -> usize {
+    line.grapheme_indices(true)
+        .nth(index)
+        .map_or(line.len(), |(i, _)| i)
+}
+
+/// Splits a line into `(start, end)` grapheme ranges that fit `width` columns. Word mode
+/// breaks after the last whitespace that fits, when there is one.
+fn segments(graphemes: &[&str], width: usize, mode: WrapMode) -> Vec<(usize, usize)> {
+    let mut segments = Vec::new();
+    let mut start = 0;
+    let mut used = 0;
+    let mut i = 0;
+    while i < graphemes.len() {
+        let w = grapheme_width(graphemes[i]);
+        if used + w > width && i > start {
+            let mut end = i;
+            if mode == WrapMode::Word {
+                if let Some(space) = (start + 1..=i)
+                    .rev()
+                    .find(|&j| graphemes[j - 1].chars().all(char::is_whitespace))
+                {
+                    end = space;
+                }
+            }
+            segments.push((start, end));
+            start = end;
+            i = end;
+            used = 0;
+            continue;
+        }
+        used += w;
+        i += 1;
+    }
+    segments.push((start, graphemes.len()));
+    segments
+}
+
+impl Object for TextAreaObject {
+    fn display(&self, backend: &mut dyn Backend, theme: &Theme) -> io::Result<()> {
+        let style = self.style.unwrap_or(theme.style(Role::Text));
+        let gutter = self.gutter_width();
+        let width = self.text_width();
+        let rows = self.visual_rows();
+        let selection = self.selection();
+
+        for screen_row in 0..self.size.1 {
+            backend.move_to(self.position.0, self.position.1 + screen_row)?;
+            let Some(&(line, start, end)) = rows.get(self.scroll.0 + screen_row) else {
+                backend.print(&" ".repeat(self.size.0), style)?;
+                continue;
+            };
+
+            if gutter > 0 {
+                let number = if start == 0 {
+                    format!("{:>1$} ", line + 1, gutter - 1)
+                } else {
+                    " ".repeat(gutter)
+                };
+                // A field narrower than the gutter shows what fits of it and no text
+                backend.print(truncate(&number, self.size.0), style.dim())?;
+            }
+            if width == 0 {
+                continue;
+            }
+
+            let graphemes: Vec<&str> = self.lines[line].graphemes(true).collect();
+            let mut x = 0;
+            let mut used = 0;
+            for (column, grapheme) in graphemes.iter().enumerate().take(end).skip(start) {
+                let w = grapheme_width(grapheme);
+                // Skip whatever is scrolled off to the left, including cut wide characters
+                if x < self.scroll.1 {
+                    x += w;
+                    if x > self.scroll.1 {
+                        backend.print(&" ".repeat(x - self.scroll.1), style)?;
+                        used += x - self.scroll.1;
+                    }
+                    continue;
+                }
+                if used + w > width {
+                    break;
+                }
+                let position = (line, column);
+                let selected = selection.is_some_and(|(a, b)| position >= a && position < b);
+                let cell_style = if self.is_selected && position == self.cursor || selected {
+                    style.reverse()
+                } else {
+                    style
+                };
+                backend.print(grapheme, cell_style)?;
+                x += w;
+                used += w;
+            }
+
+            // The cursor past the end of the row gets a cell of its own
+            let at_end = self.cursor == (line, end) && end == graphemes.len();
+            if self.is_selected && at_end && used < width && x >= self.scroll.1 {
+                backend.print(" ", style.reverse())?;
+                used += 1;
+            }
+            backend.print(&" ".repeat(width.saturating_sub(used)), style)?;
+        }
+        Ok(())
+    }
+
+    fn handle(&mut self, command: ObjectCommand) -> Result<HandleReturn, ()> {
+        match command {
+            ObjectCommand::SetSize((width, height)) => {
+                self.size = (width, height);
+                self.scroll_to_cursor();
+                Ok(HandleReturn::None)
+            }
+            ObjectCommand::GetSize() => Ok(HandleReturn::Size(self.size)),
+            ObjectCommand::SetPosition((x, y)) => {
+                self.position = (x, y);
+                Ok(HandleReturn::None)
+            }
+            ObjectCommand::GetPosition() => Ok(HandleReturn::Position(self.position)),
+            ObjectCommand::SetText(text) | ObjectCommand::SetValue(Value::Text(text)) => {
+                self.set_text(&text);
+                Ok(HandleReturn::None)
+            }
+            ObjectCommand::GetValue() => Ok(HandleReturn::Value(Value::Text(self.text()))),
+            ObjectCommand::SetWrap(wrap) => {
+                self.wrap = wrap;
+                self.scroll_to_cursor();
+                Ok(HandleReturn::None)
+            }
+            ObjectCommand::SetLineNumbers(line_numbers) => {
+                self.line_numbers = line_numbers;
+                self.scroll_to_cursor();
+                Ok(HandleReturn::None)
+            }
+            ObjectCommand::GetSelected() => Ok(HandleReturn::Selected(self.is_selected)),
+            ObjectCommand::SetSelected(selected) => {
+                self.is_selected = selected;
+                Ok(HandleReturn::None)
+            }
+            ObjectCommand::SetStyle(style) => {
+                self.style = Some(style);
+                Ok(HandleReturn::None)
+            }
+            ObjectCommand::KeyPress(key) => self.handle_key(key),
+            _ => Err(()),
+        }
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+
+    fn is_selectable(&self) -> bool {
+        true
+    }
+}
+
+impl Selectable for TextAreaObject {}
+
+#[cfg(test)]
+mod tests {
+    use super::TextAreaObject;
+    use crate::backend::test::TestBackend;
+    use crate::backend::Backend;
+    use crate::objects::{Object, ObjectCommand};
+    use crate::style::Style;
+    use crate::theme::Theme;
+
+    #[test]
+    fn narrow_gutter_stays_inside_the_field() {
+        let mut backend = TestBackend::new(5, 1);
+        backend.print("xxxxx", Style::default()).unwrap();
+
+        let text = ["line"; 10].join("\n");
+        let mut area = TextAreaObject::new(&text);
+        area.handle(ObjectCommand::SetLineNumbers(true)).unwrap();
+        area.handle(ObjectCommand::SetSize((2, 1))).unwrap();
+        area.display(&mut backend, &Theme::default()).unwrap();
+
+        assert_eq!(backend.line(0), " 1xxx");
+    }
+}
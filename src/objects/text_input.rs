@@ -0,0 +1,284 @@
+use crate::backend::Backend;
+use crate::objects::{HandleReturn, Object, ObjectCommand, Selectable, Value};
+use crate::style::Style;
+use crate::theme::{Role, Theme};
+use crate::wrap::{display_width, grapheme_width};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::io;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// A one-line editable field. Keys reach it while it is `TuiHandler::selected`.
+pub struct TextInputObject {
+    pub value: String,
+    /// Cursor position, in grapheme clusters from the start of `value`.
+    pub cursor: usize,
+    /// The first grapheme cluster shown, so the cursor stays in view.
+    scroll: usize,
+    pub placeholder: String,
+    /// Shown in place of every character, e.g. `Some('*')` for passwords.
+    pub mask: Option<char>,
+    /// Most grapheme clusters the value may hold.
+    pub max_length: Option<usize>,
+    pub is_selected: bool,
+    pub size: (usize, usize),
+    pub position: (usize, usize),
+    pub style: Option<Style>,
+}
+
+impl TextInputObject {
+    pub fn new(placeholder: &str) -> Box<Self> {
+        Box::new(TextInputObject {
+            value: String::new(),
+            cursor: 0,
+            scroll: 0,
+            placeholder: placeholder.to_string(),
+            mask: None,
+            max_length: None,
+            is_selected: false,
+            size: (0, 0),
+            position: (0, 0),
+            style: None,
+        })
+    }
+
+    fn graphemes(&self) -> Vec<&str> {
+        self.value.graphemes(true).collect()
+    }
+
+    /// Byte offset of grapheme `index`, or the end of the value.
+    fn byte_index(&self, index: usize) -> usize {
+        self.value
+            .grapheme_indices(true)
+            .nth(index)
+            .map_or(self.value.len(), |(i, _)| i)
+    }
+
+    fn cell_width(&self, grapheme: &str) -> usize {
+        match self.mask {
+            Some(_) => 1,
+            None => grapheme_width(grapheme),
+        }
+    }
+
+    fn set_value(&mut self, value: String) {
+        self.value = match self.max_length {
+            Some(max) => value.graphemes(true).take(max).collect(),
+            None => value,
+        };
+        self.cursor = self.graphemes().len();
+        self.scroll_to_cursor();
+    }
+
+    fn insert(&mut self, c: char) {
+        let count = self.graphemes().len();
+        if self.max_length.is_some_and(|max| count >= max) {
+            return;
+        }
+        let at = self.byte_index(self.cursor);
+        self.value.insert(at, c);
+        // A combining mark joins the cluster before it rather than adding one
+        if self.graphemes().len() > count {
+            self.cursor += 1;
+        }
+    }
+
+    fn remove(&mut self, index: usize) {
+        let start = self.byte_index(index);
+        let end = self.byte_index(index + 1);
+        self.value.replace_range(start..end, "");
+    }
+
+    /// Keeps the cursor cell inside the field, scrolling as little as possible.
+    fn scroll_to_cursor(&mut self) {
+        let width = self.size.0;
+        if self.cursor < self.scroll {
+            self.scroll = self.cursor;
+        }
+        let graphemes = self.graphemes();
+        let widths: Vec<usize> = graphemes.iter().map(|g| self.cell_width(g)).collect();
+        let cursor_width = widths.get(self.cursor).copied().unwrap_or(1).max(1);
+        while self.scroll < self.cursor
+            && widths[self.scroll..self.cursor].iter().sum::<usize>() + cursor_width > width
+        {
+            self.scroll += 1;
+        }
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> Result<HandleReturn, ()> {
+        let word = key.modifiers.contains(KeyModifiers::CONTROL);
+        let len = self.graphemes().len();
+        match key.code {
+            KeyCode::Char(c) if !word && !key.modifiers.contains(KeyModifiers::ALT) => {
+                self.insert(c)
+            }
+            KeyCode::Backspace if self.cursor > 0 => {
+                self.cursor -= 1;
+                self.remove(self.cursor);
+            }
+            KeyCode::Backspace => {}
+            KeyCode::Delete if self.cursor < len => self.remove(self.cursor),
+            KeyCode::Delete => {}
+            // Word jumps would give away where the spaces are in a masked value
+            KeyCode::Left if word && self.mask.is_some() => self.cursor = 0,
+            KeyCode::Right if word && self.mask.is_some() => self.cursor = len,
+            KeyCode::Left if word => self.cursor = word_left(&self.graphemes(), self.cursor),
+            KeyCode::Right if word => self.cursor = word_right(&self.graphemes(), self.cursor),
+            KeyCode::Left => self.cursor = self.cursor.saturating_sub(1),
+            KeyCode::Right => self.cursor = (self.cursor + 1).min(len),
+            KeyCode::Home => self.cursor = 0,
+            KeyCode::End => self.cursor = len,
+            _ => return Err(()),
+        }
+        self.scroll_to_cursor();
+        Ok(HandleReturn::None)
+    }
+}
+
+fn is_space(grapheme: &str) -> bool {
+    grapheme.chars().all(char::is_whitespace)
+}
+
+/// Start of the word before `cursor`, skipping any whitespace in between.
+pub(crate) fn word_left(graphemes: &[&str], mut cursor: usize) -> usize {
+    while cursor > 0 && is_space(graphemes[cursor - 1]) {
+        cursor -= 1;
+    }
+    while cursor > 0 && !is_space(graphemes[cursor - 1]) {
+        cursor -= 1;
+    }
+    cursor
+}
+
+/// Start of the word after `cursor`.
+pub(crate) fn word_right(graphemes: &[&str], mut cursor: usize) -> usize {
+    while cursor < graphemes.len() && !is_space(graphemes[cursor]) {
+        cursor += 1;
+    }
+    while cursor < graphemes.len() && is_space(graphemes[cursor]) {
+        cursor += 1;
+    }
+    cursor
+}
+
+impl Object for TextInputObject {
+    fn display(&self, backend: &mut dyn Backend, theme: &Theme) -> io::Result<()> {
+        let style = self.style.unwrap_or(theme.style(Role::Text));
+        let width = self.size.0;
+        if width == 0 || self.size.1 == 0 {
+            return Ok(());
+        }
+        backend.move_to(self.position.0, self.position.1)?;
+
+        if self.value.is_empty() && !self.placeholder.is_empty() {
+            let mut used = 0;
+            let mut text = String::new();
+            for grapheme in self.placeholder.graphemes(true) {
+                let w = grapheme_width(grapheme);
+                if used + w > width {
+                    break;
+                }
+                text.push_str(grapheme);
+                used += w;
+            }
+            if self.is_selected {
+                // The cursor sits on the first placeholder cell
+                let first = text.graphemes(true).next().unwrap_or(" ").to_string();
+                backend.print(&first, style.reverse())?;
+                used = used.max(1);
+                text = text[first.len().min(text.len())..].to_string();
+            }
+            backend.print(&text, style.dim())?;
+            return backend.print(&" ".repeat(width.saturating_sub(used)), style);
+        }
+
+        let mask = self.mask.map(String::from);
+        let mut used = 0;
+        for (i, grapheme) in self.graphemes().into_iter().enumerate().skip(self.scroll) {
+            let shown = mask.as_deref().unwrap_or(grapheme);
+            let w = self.cell_width(grapheme);
+            if used + w > width {
+                break;
+            }
+            let cell_style = if self.is_selected && i == self.cursor {
+                style.reverse()
+            } else {
+                style
+            };
+            backend.print(shown, cell_style)?;
+            used += w;
+        }
+
+        if self.is_selected && self.cursor == self.graphemes().len() && used < width {
+            backend.print(" ", style.reverse())?;
+            used += 1;
+        }
+        backend.print(&" ".repeat(width - used), style)
+    }
+
+    fn handle(&mut self, command: ObjectCommand) -> Result<HandleReturn, ()> {
+        match command {
+            ObjectCommand::SetSize((width, height)) => {
+                self.size = (width, height);
+                self.scroll_to_cursor();
+                Ok(HandleReturn::None)
+            }
+            ObjectCommand::GetSize() => Ok(HandleReturn::Size(self.size)),
+            ObjectCommand::SetPosition((x, y)) => {
+                self.position = (x, y);
+                Ok(HandleReturn::None)
+            }
+            ObjectCommand::GetPosition() => Ok(HandleReturn::Position(self.position)),
+            ObjectCommand::SetText(text) | ObjectCommand::SetValue(Value::Text(text)) => {
+                self.set_value(text);
+                Ok(HandleReturn::None)
+            }
+            ObjectCommand::GetValue() => Ok(HandleReturn::Value(Value::Text(self.value.clone()))),
+            ObjectCommand::SetPlaceholder(placeholder) => {
+                self.placeholder = placeholder;
+                Ok(HandleReturn::None)
+            }
+            ObjectCommand::SetMask(mask) => {
+                self.mask = mask;
+                self.scroll_to_cursor();
+                Ok(HandleReturn::None)
+            }
+            ObjectCommand::SetMaxLength(max_length) => {
+                self.max_length = max_length;
+                let value = std::mem::take(&mut self.value);
+                self.set_value(value);
+                Ok(HandleReturn::None)
+            }
+            ObjectCommand::GetSelected() => Ok(HandleReturn::Selected(self.is_selected)),
+            ObjectCommand::SetSelected(selected) => {
+                self.is_selected = selected;
+                Ok(HandleReturn::None)
+            }
+            ObjectCommand::SetStyle(style) => {
+                self.style = Some(style);
+                Ok(HandleReturn::None)
+            }
+            ObjectCommand::KeyPress(key) => self.handle_key(key),
+            _ => Err(()),
+        }
+    }
+
+    fn natural_size(&self) -> Option<(usize, usize)> {
+        // Room for the cursor after the last character
+        let width = display_width(&self.value).max(display_width(&self.placeholder));
+        Some((width + 1, 1))
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+
+    fn is_selectable(&self) -> bool {
+        true
+    }
+}
+
+impl Selectable for TextInputObject {}
@@ -1,17 +1,36 @@
-use crate::objects::ObjectCommand;
-
 use super::{
     objects::container::*,
+    objects::{ObjectCommand, SelectionDirection},
     triggers::{MouseTriggers, Trigger},
-    TuiHandler,
+    TuiHandler, TICK_RATE,
 };
-use crossterm::event::MouseEventKind;
-use crossterm::event::{self, Event, KeyCode};
+use crate::backend::crossterm::restore_terminal;
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind};
+use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+use crossterm::terminal::is_raw_mode_enabled;
+use std::io;
+use std::sync::Once;
+
+static PANIC_HOOK: Once = Once::new();
+
+/// Chains a panic hook that restores the terminal before the panic message is printed,
+/// so a panic inside the event loop doesn't leave the shell in raw mode.
+pub(crate) fn install_panic_hook() {
+    PANIC_HOOK.call_once(|| {
+        let previous = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            if is_raw_mode_enabled().unwrap_or(false) {
+                let _ = restore_terminal();
+            }
+            previous(info);
+        }));
+    });
+}
 
 impl TuiHandler {
-    pub fn handle_term_events(&mut self) {
-        if event::poll(std::time::Duration::from_millis(500)).unwrap() {
-            let event = event::read().unwrap();
+    pub fn handle_term_events(&mut self) -> io::Result<()> {
+        if event::poll(TICK_RATE)? {
+            let event = event::read()?;
             match event {
                 Event::Resize(width, height) => {
                     println!("Terminal resized to {}x{}", width, height);
@@ -23,8 +42,19 @@ impl TuiHandler {
                         .size = (width as usize, height as usize);
                     self.changed = true;
                 }
+                Event::Key(key_event) if self.has_dialog() => self.dialog_key(key_event),
+                Event::Mouse(mouse_event) if self.has_dialog() => {
+                    if let MouseEventKind::Down(MouseButton::Left) = mouse_event.kind {
+                        let (x, y) = (mouse_event.column as usize, mouse_event.row as usize);
+                        let hit = self.dialog_mut().and_then(|dialog| dialog.button_at(x, y));
+                        if hit.is_some() {
+                            self.answer_dialog(hit);
+                        }
+                    }
+                }
+                Event::Key(key_event) if self.send_key(key_event) => self.changed = true,
                 Event::Key(key_event) => {
-                    if key_event.kind == crossterm::event::KeyEventKind::Release {
+                    if key_event.kind == KeyEventKind::Release {
                         let mut triggers = std::mem::take(&mut self.triggers);
                         for (trigger, callback) in &mut triggers {
                             if let Trigger::KeyPress(c) = trigger {
@@ -40,6 +70,7 @@ impl TuiHandler {
                     }
                 }
                 Event::Mouse(mouse_event) => {
+                    self.send_mouse(mouse_event);
                     let mut triggers = std::mem::take(&mut self.triggers);
                     for (trigger, callback) in &mut triggers {
                         if let Trigger::MouseClick(mouse_trigger) = trigger {
@@ -63,22 +94,11 @@ impl TuiHandler {
                                         let x = mouse_event.column as usize;
                                         let y = mouse_event.row as usize;
 
-                                        let (obj_x, obj_y) = self
-                                            .with(object_handle)
-                                            .handle(ObjectCommand::GetPosition())
-                                            .unwrap()
-                                            .unwrap_position();
-                                        let (obj_width, obj_height) = self
-                                            .with(object_handle)
-                                            .handle(ObjectCommand::GetSize())
-                                            .unwrap()
-                                            .unwrap_size();
-
-                                        if x >= obj_x
-                                            && x < obj_x + obj_width
-                                            && y >= obj_y
-                                            && y < obj_y + obj_height
-                                        {
+                                        // Only the topmost object under the cursor gets the click
+                                        let hit = self.object_at(x, y);
+                                        if hit.is_some_and(|hit| {
+                                            hit.indexes.starts_with(&object_handle.indexes)
+                                        }) {
                                             callback(self);
                                             self.changed = true;
                                         }
@@ -95,5 +115,226 @@ impl TuiHandler {
                 _ => {}
             }
         }
+        Ok(())
+    }
+
+    /// Offers a key to the selected object, then to each of its parents. Returns whether
+    /// one of them used it, in which case the key triggers don't see it.
+    fn send_key(&mut self, key_event: KeyEvent) -> bool {
+        if key_event.kind == KeyEventKind::Release {
+            // The object already had the press
+            return self.consumed_keys.remove(&key_event.code);
+        }
+
+        let mut target = self.selected.clone();
+        if target.indexes.is_empty() || !self.is_visible(&target) {
+            return false;
+        }
+        while !target.indexes.is_empty() {
+            if self
+                .with(&target)
+                .handle(ObjectCommand::KeyPress(key_event))
+                .is_ok()
+            {
+                self.consumed_keys.insert(key_event.code);
+                self.keep_selection_visible();
+                return true;
+            }
+            target.indexes.pop();
+        }
+        false
+    }
+
+    /// Hands a mouse event to the object under it, or the nearest parent that takes it.
+    /// A click also selects the object if it can be selected, after it has seen the
+    /// click so it can tell whether it already had the focus.
+    fn send_mouse(&mut self, mouse_event: MouseEvent) {
+        let (x, y) = (mouse_event.column as usize, mouse_event.row as usize);
+        let Some(hit) = self.object_at(x, y) else {
+            return;
+        };
+
+        let mut target = hit.clone();
+        let mut handled = false;
+        while !target.indexes.is_empty() {
+            // Objects inside a scroll view get the cell in their own, unscrolled layout
+            let mut event = mouse_event;
+            if let Some((dx, dy)) = self.scroll_offset_at(&target) {
+                event.column = (x + dx).min(u16::MAX as usize) as u16;
+                event.row = (y + dy).min(u16::MAX as usize) as u16;
+            }
+            if self
+                .with(&target)
+                .handle(ObjectCommand::Mouse(event))
+                .is_ok()
+            {
+                handled = true;
+                break;
+            }
+            target.indexes.pop();
+        }
+
+        if let MouseEventKind::Down(MouseButton::Left) = mouse_event.kind {
+            if self.selected.indexes != hit.indexes && self.selectable_at(&hit) {
+                self.set_selected(&hit);
+                self.changed = true;
+            }
+        }
+        if handled {
+            self.changed = true;
+            self.keep_selection_visible();
+        }
+    }
+
+    /// Keys while a dialog is open never reach the triggers: arrows and Tab move between
+    /// its buttons, Enter or Space picks the focused one and Esc cancels.
+    fn dialog_key(&mut self, key_event: KeyEvent) {
+        if key_event.kind == KeyEventKind::Release {
+            self.consumed_keys.remove(&key_event.code);
+            return;
+        }
+        // The release may come after the dialog is gone and mustn't reach the triggers
+        self.consumed_keys.insert(key_event.code);
+        let direction = match key_event.code {
+            KeyCode::Left | KeyCode::Up | KeyCode::BackTab => SelectionDirection::Left,
+            KeyCode::Right | KeyCode::Down | KeyCode::Tab => SelectionDirection::Right,
+            KeyCode::Enter | KeyCode::Char(' ') => {
+                let focused = self.dialog_mut().map(|dialog| dialog.focused);
+                self.answer_dialog(focused);
+                return;
+            }
+            KeyCode::Esc => {
+                self.answer_dialog(None);
+                return;
+            }
+            _ => return,
+        };
+        self.selectable_movement(direction);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::backend::test::TestBackend;
+    use crate::objects::button::ButtonObject;
+    use crate::objects::dialog::DialogObject;
+    use crate::objects::list::ListObject;
+    use crate::objects::tabs::TabsObject;
+    use crate::objects::text::TextObject;
+    use crate::objects::text_input::TextInputObject;
+    use crate::objects::{Handle, ObjectCommand, SelectionDirection, Value};
+    use crate::TuiHandler;
+    use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+    use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    #[test]
+    fn answering_a_dialog_swallows_the_key_release() {
+        let mut tui = TuiHandler::with_backend(Box::new(TestBackend::new(40, 10)));
+        tui.open_dialog(DialogObject::ok("Saved", "Done", Box::new(|_, _| {})));
+
+        let press = KeyEvent::new(KeyCode::Char(' '), KeyModifiers::NONE);
+        let mut release = press;
+        release.kind = KeyEventKind::Release;
+        tui.dialog_key(press);
+        assert!(!tui.has_dialog());
+        // Swallowed, so the `' '` key trigger doesn't fire once the dialog is gone
+        assert!(tui.send_key(release));
+    }
+
+    #[test]
+    fn first_click_on_a_list_only_focuses_it() {
+        let mut tui = TuiHandler::with_backend(Box::new(TestBackend::new(20, 6)));
+        let activated = Rc::new(Cell::new(0));
+        let count = activated.clone();
+        let items = vec!["one".to_string(), "two".to_string()];
+        let list = ListObject::new(items, Box::new(move |_| count.set(count.get() + 1)));
+        let root = Handle {
+            indexes: Vec::new(),
+        };
+        let list = tui
+            .with(&root)
+            .handle(ObjectCommand::AddObject(list))
+            .unwrap()
+            .unwrap_handle();
+        tui.draw().unwrap();
+
+        let (x, y) = tui
+            .with(&list)
+            .handle(ObjectCommand::GetPosition())
+            .unwrap()
+            .unwrap_position();
+        let click = MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column: x as u16,
+            row: y as u16,
+            modifiers: KeyModifiers::NONE,
+        };
+        tui.send_mouse(click);
+        assert_eq!(tui.selected.indexes, list.indexes);
+        assert_eq!(activated.get(), 0);
+        tui.send_mouse(click);
+        assert_eq!(activated.get(), 1);
+    }
+
+    #[test]
+    fn moving_after_switching_to_a_tab_with_nothing_selectable() {
+        let mut tui = TuiHandler::with_backend(Box::new(TestBackend::new(20, 6)));
+        let root = Handle {
+            indexes: Vec::new(),
+        };
+        let tabs = tui
+            .with(&root)
+            .handle(ObjectCommand::AddObject(TabsObject::new()))
+            .unwrap()
+            .unwrap_handle();
+        let button = tui
+            .with(&tabs)
+            .handle(ObjectCommand::AddTab(
+                "A".to_string(),
+                ButtonObject::new("a", Box::new(|| {})),
+            ))
+            .unwrap()
+            .unwrap_handle();
+        tui.with(&tabs)
+            .handle(ObjectCommand::AddTab("B".to_string(), TextObject::new("b")))
+            .unwrap();
+        tui.set_selected(&button);
+
+        let next = KeyEvent::new(KeyCode::PageDown, KeyModifiers::CONTROL);
+        assert!(tui.send_key(next));
+        assert!(tui.selected.indexes.is_empty());
+        tui.selectable_movement(SelectionDirection::Down);
+        assert!(tui.selected.indexes.is_empty());
+
+        // Once something can be selected again, movement picks it up
+        tui.with(&tabs)
+            .handle(ObjectCommand::SetValue(Value::Index(0)))
+            .unwrap();
+        tui.selectable_movement(SelectionDirection::Down);
+        assert_eq!(tui.selected.indexes, button.indexes);
+    }
+
+    #[test]
+    fn overlapping_keystrokes_keep_every_release_from_the_triggers() {
+        let mut tui = TuiHandler::with_backend(Box::new(TestBackend::new(20, 3)));
+        let root = Handle {
+            indexes: Vec::new(),
+        };
+        let input = tui
+            .with(&root)
+            .handle(ObjectCommand::AddObject(TextInputObject::new("")))
+            .unwrap()
+            .unwrap_handle();
+        tui.set_selected(&input);
+
+        let key = |c, kind| KeyEvent::new_with_kind(KeyCode::Char(c), KeyModifiers::NONE, kind);
+        assert!(tui.send_key(key('q', KeyEventKind::Press)));
+        assert!(tui.send_key(key('a', KeyEventKind::Press)));
+        assert!(tui.send_key(key('q', KeyEventKind::Release)));
+        assert!(tui.send_key(key('a', KeyEventKind::Release)));
+        // A release with no consumed press is left for the triggers
+        assert!(!tui.send_key(key('q', KeyEventKind::Release)));
     }
 }
@@ -0,0 +1,249 @@
+use crate::style::{Color, Style};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    Border,
+    Text,
+    Button,
+    ButtonSelected,
+    Title,
+    Error,
+}
+
+/// The app-wide look. Objects fall back to these styles unless they were given their own.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Theme {
+    pub name: String,
+    pub border: Style,
+    pub text: Style,
+    pub button: Style,
+    pub button_selected: Style,
+    pub title: Style,
+    pub error: Style,
+}
+
+impl Theme {
+    pub fn style(&self, role: Role) -> Style {
+        match role {
+            Role::Border => self.border,
+            Role::Text => self.text,
+            Role::Button => self.button,
+            Role::ButtonSelected => self.button_selected,
+            Role::Title => self.title,
+            Role::Error => self.error,
+        }
+    }
+
+    pub fn set_style(&mut self, role: Role, style: Style) {
+        match role {
+            Role::Border => self.border = style,
+            Role::Text => self.text = style,
+            Role::Button => self.button = style,
+            Role::ButtonSelected => self.button_selected = style,
+            Role::Title => self.title = style,
+            Role::Error => self.error = style,
+        }
+    }
+
+    pub fn dark() -> Self {
+        Theme {
+            name: "dark".to_string(),
+            border: Style::new().fg(Color::BrightBlack),
+            text: Style::new().fg(Color::White),
+            button: Style::new().fg(Color::BrightWhite),
+            button_selected: Style::new().fg(Color::Black).bg(Color::Cyan),
+            title: Style::new().fg(Color::BrightCyan).bold(),
+            error: Style::new().fg(Color::BrightRed).bold(),
+        }
+    }
+
+    pub fn light() -> Self {
+        Theme {
+            name: "light".to_string(),
+            border: Style::new().fg(Color::BrightBlack),
+            text: Style::new().fg(Color::Black),
+            button: Style::new().fg(Color::Blue),
+            button_selected: Style::new().fg(Color::BrightWhite).bg(Color::Blue),
+            title: Style::new().fg(Color::Blue).bold(),
+            error: Style::new().fg(Color::Red).bold(),
+        }
+    }
+
+    /// One of the built-in themes: `default`, `dark` or `light`.
+    pub fn builtin(name: &str) -> Option<Self> {
+        match name {
+            "default" => Some(Theme::default()),
+            "dark" => Some(Theme::dark()),
+            "light" => Some(Theme::light()),
+            _ => None,
+        }
+    }
+
+    pub fn from_toml(source: &str) -> Result<Self, ThemeError> {
+        let file: ThemeFile =
+            toml::from_str(source).map_err(|e| ThemeError::Parse(e.to_string()))?;
+        file.resolve()
+    }
+
+    pub fn from_json(source: &str) -> Result<Self, ThemeError> {
+        let file: ThemeFile =
+            serde_json::from_str(source).map_err(|e| ThemeError::Parse(e.to_string()))?;
+        file.resolve()
+    }
+
+    /// Loads a theme file, picking the format from the `.toml` or `.json` extension.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ThemeError> {
+        let path = path.as_ref();
+        let source = std::fs::read_to_string(path).map_err(ThemeError::Io)?;
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("toml") => Theme::from_toml(&source),
+            Some("json") => Theme::from_json(&source),
+            _ => Err(ThemeError::UnknownFormat(path.display().to_string())),
+        }
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme {
+            name: "default".to_string(),
+            border: Style::default(),
+            text: Style::default(),
+            button: Style::default(),
+            button_selected: Style::new().reverse(),
+            title: Style::new().bold(),
+            error: Style::new().fg(Color::Red),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ThemeError {
+    Io(std::io::Error),
+    Parse(String),
+    UnknownFormat(String),
+    UnknownColor(String),
+    UnknownBase(String),
+}
+
+impl fmt::Display for ThemeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ThemeError::Io(e) => write!(f, "could not read theme: {}", e),
+            ThemeError::Parse(e) => write!(f, "could not parse theme: {}", e),
+            ThemeError::UnknownFormat(path) => write!(f, "unknown theme format: {}", path),
+            ThemeError::UnknownColor(color) => write!(f, "unknown color: {}", color),
+            ThemeError::UnknownBase(name) => write!(f, "unknown base theme: {}", name),
+        }
+    }
+}
+
+impl std::error::Error for ThemeError {}
+
+// On-disk layout of a theme. Colors are names, palette indexes, hex strings, or
+// entries of the file's own `palette` table, e.g.
+//
+//     name = "solarized"
+//     base = "dark"
+//
+//     [palette]
+//     accent = "#268bd2"
+//
+//     [button-selected]
+//     fg = "black"
+//     bg = "accent"
+//     bold = true
+#[derive(Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct ThemeFile {
+    name: Option<String>,
+    base: Option<String>,
+    #[serde(default)]
+    palette: HashMap<String, ColorValue>,
+    border: Option<StyleFile>,
+    text: Option<StyleFile>,
+    button: Option<StyleFile>,
+    button_selected: Option<StyleFile>,
+    title: Option<StyleFile>,
+    error: Option<StyleFile>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct StyleFile {
+    fg: Option<ColorValue>,
+    bg: Option<ColorValue>,
+    bold: bool,
+    italic: bool,
+    underline: bool,
+    dim: bool,
+    reverse: bool,
+}
+
+#[derive(Deserialize, Clone)]
+#[serde(untagged)]
+enum ColorValue {
+    Index(u8),
+    Name(String),
+}
+
+impl ThemeFile {
+    fn resolve(self) -> Result<Theme, ThemeError> {
+        let mut theme = match &self.base {
+            Some(base) => {
+                Theme::builtin(base).ok_or_else(|| ThemeError::UnknownBase(base.clone()))?
+            }
+            None => Theme::default(),
+        };
+        if let Some(name) = &self.name {
+            theme.name = name.clone();
+        }
+
+        let roles = [
+            (Role::Border, &self.border),
+            (Role::Text, &self.text),
+            (Role::Button, &self.button),
+            (Role::ButtonSelected, &self.button_selected),
+            (Role::Title, &self.title),
+            (Role::Error, &self.error),
+        ];
+        for (role, style) in roles {
+            if let Some(style) = style {
+                theme.set_style(role, self.resolve_style(style)?);
+            }
+        }
+        Ok(theme)
+    }
+
+    fn resolve_style(&self, style: &StyleFile) -> Result<Style, ThemeError> {
+        let color = |value: &Option<ColorValue>| -> Result<Option<Color>, ThemeError> {
+            value.as_ref().map(|v| self.resolve_color(v, 0)).transpose()
+        };
+        Ok(Style {
+            fg: color(&style.fg)?,
+            bg: color(&style.bg)?,
+            bold: style.bold,
+            italic: style.italic,
+            underline: style.underline,
+            dim: style.dim,
+            reverse: style.reverse,
+        })
+    }
+
+    fn resolve_color(&self, value: &ColorValue, depth: usize) -> Result<Color, ThemeError> {
+        match value {
+            ColorValue::Index(index) => Ok(Color::Indexed(*index)),
+            ColorValue::Name(name) => match self.palette.get(name) {
+                // Palette entries may point at each other; the depth check stops cycles
+                Some(entry) if depth < self.palette.len() => self.resolve_color(entry, depth + 1),
+                _ => name
+                    .parse()
+                    .map_err(|_| ThemeError::UnknownColor(name.clone())),
+            },
+        }
+    }
+}
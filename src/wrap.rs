@@ -0,0 +1,167 @@
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+/// How many terminal columns `text` takes up.
+pub fn display_width(text: &str) -> usize {
+    text.graphemes(true).map(grapheme_width).sum()
+}
+
+pub fn grapheme_width(grapheme: &str) -> usize {
+    if grapheme.chars().any(char::is_control) {
+        return 0;
+    }
+    UnicodeWidthStr::width(grapheme)
+}
+
+/// Breaks `text` into lines of at most `width` columns without splitting grapheme clusters.
+/// A cluster wider than `width` gets a line to itself.
+pub fn wrap_chars(text: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    if width == 0 {
+        return lines;
+    }
+
+    let mut line = String::new();
+    let mut line_width = 0;
+    for grapheme in text.graphemes(true) {
+        let w = grapheme_width(grapheme);
+        if line_width + w > width && !line.is_empty() {
+            lines.push(std::mem::take(&mut line));
+            line_width = 0;
+        }
+        line.push_str(grapheme);
+        line_width += w;
+    }
+    if !line.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+/// The longest prefix of `text` that fits in `width` columns.
+pub fn truncate(text: &str, width: usize) -> &str {
+    let mut used = 0;
+    for (i, grapheme) in text.grapheme_indices(true) {
+        used += grapheme_width(grapheme);
+        if used > width {
+            return &text[..i];
+        }
+    }
+    text
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WrapMode {
+    /// Lines only break at `\n`; anything too wide is handled by the overflow mode.
+    None,
+    #[default]
+    Character,
+    Word,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Overflow {
+    #[default]
+    Clip,
+    /// Marks cut-off text with a trailing `…`.
+    Ellipsis,
+}
+
+/// Splits `text` on `\n` and breaks each line to fit `width` according to `mode`.
+pub fn wrap(text: &str, width: usize, mode: WrapMode) -> Vec<String> {
+    let mut lines = Vec::new();
+    for paragraph in text.lines() {
+        match mode {
+            WrapMode::None => lines.push(paragraph.to_string()),
+            WrapMode::Character => {
+                let wrapped = wrap_chars(paragraph, width);
+                if wrapped.is_empty() {
+                    lines.push(String::new());
+                }
+                lines.extend(wrapped);
+            }
+            WrapMode::Word => lines.extend(wrap_words(paragraph, width)),
+        }
+    }
+    lines
+}
+
+/// Greedy word wrap. Whitespace at a break is dropped, and words longer than `width`
+/// fall back to character wrapping.
+pub fn wrap_words(text: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    let mut line_width = 0;
+
+    for token in split_whitespace_runs(text) {
+        let token_width = display_width(token);
+        let is_space = token.chars().all(char::is_whitespace);
+
+        if line_width + token_width <= width {
+            if !(is_space && line.is_empty() && !lines.is_empty()) {
+                line.push_str(token);
+                line_width += token_width;
+            }
+            continue;
+        }
+
+        if is_space {
+            lines.push(std::mem::take(&mut line).trim_end().to_string());
+            line_width = 0;
+            continue;
+        }
+
+        // Indentation that leaves no room for the word isn't worth a line of its own
+        if !line.trim().is_empty() {
+            lines.push(line.trim_end().to_string());
+        }
+        line.clear();
+        line_width = 0;
+        if token_width <= width {
+            line.push_str(token);
+            line_width = token_width;
+        } else {
+            let mut pieces = wrap_chars(token, width);
+            if let Some(last) = pieces.pop() {
+                lines.extend(pieces);
+                line_width = display_width(&last);
+                line = last;
+            }
+        }
+    }
+
+    if !line.is_empty() || lines.is_empty() {
+        lines.push(line.trim_end().to_string());
+    }
+    lines
+}
+
+fn split_whitespace_runs(text: &str) -> Vec<&str> {
+    let mut tokens = Vec::new();
+    let mut start = 0;
+    let mut in_space = None;
+    for (i, c) in text.char_indices() {
+        let space = c.is_whitespace();
+        if in_space.is_some_and(|s| s != space) {
+            tokens.push(&text[start..i]);
+            start = i;
+        }
+        in_space = Some(space);
+    }
+    if start < text.len() {
+        tokens.push(&text[start..]);
+    }
+    tokens
+}
+
+/// Cuts `text` down to `width` columns, ending it with `…` when `overflow` asks for it.
+pub fn fit(text: &str, width: usize, overflow: Overflow) -> String {
+    if display_width(text) <= width {
+        return text.to_string();
+    }
+    match overflow {
+        Overflow::Clip => truncate(text, width).to_string(),
+        Overflow::Ellipsis if width == 0 => String::new(),
+        Overflow::Ellipsis => format!("{}…", truncate(text, width - 1)),
+    }
+}